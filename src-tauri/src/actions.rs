@@ -0,0 +1,244 @@
+use crate::error::AppResult;
+use crate::events::{emit_main_requests_queued, emit_timer, TimerEvent};
+use crate::{next_mode_after_skip, perform_open_main, show_system_notification, AppState, PomodoroState};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+#[derive(Clone, Copy, Serialize, Deserialize, Type, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum MainView {
+  Inbox,
+  Today,
+  Review,
+  /// The Today workspace's calendar.
+  Calendar,
+}
+
+impl MainView {
+  pub fn from_name(name: &str) -> Option<Self> {
+    match name {
+      "inbox" => Some(Self::Inbox),
+      "today" => Some(Self::Today),
+      "review" => Some(Self::Review),
+      "calendar" => Some(Self::Calendar),
+      _ => None,
+    }
+  }
+}
+
+/// Work for the main window's frontend, which owns tasks and navigation; queued because the window may still be loading.
+#[derive(Clone, Serialize, Deserialize, Type, Debug, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum MainRequest {
+  Navigate { view: MainView },
+  Capture { text: String },
+  /// Binds the timer to the todo with this title, creating it in the Inbox when there is none.
+  FocusTask { title: String },
+  /// An Inbox item with notes: a file from the drop folder or a page clipped in the browser.
+  ImportNote { title: String, notes: Option<String> },
+}
+
+impl MainRequest {
+  /// Captured text and imported notes exist nowhere else until the frontend has stored them.
+  fn is_durable(&self) -> bool {
+    matches!(self, Self::Capture { .. } | Self::ImportNote { .. })
+  }
+}
+
+/// Requests the main window has not taken yet. The durable ones are persisted to `main_requests.json`, so they survive
+/// quitting while the window stays hidden in the tray or was never opened (`--minimized`).
+pub struct MainRequestQueue {
+  path: PathBuf,
+  requests: Mutex<Vec<MainRequest>>,
+}
+
+impl MainRequestQueue {
+  pub fn load(path: PathBuf) -> Self {
+    let requests = fs::read_to_string(&path)
+      .ok()
+      .and_then(|content| serde_json::from_str::<Vec<MainRequest>>(&content).ok())
+      .unwrap_or_default();
+    Self { path, requests: Mutex::new(requests) }
+  }
+
+  fn save(&self, requests: &[MainRequest]) -> AppResult<()> {
+    let durable = requests.iter().filter(|request| request.is_durable()).collect::<Vec<_>>();
+    fs::write(&self.path, serde_json::to_string_pretty(&durable)?)?;
+    Ok(())
+  }
+
+  /// Persists the queue with the request added before keeping it, so a failed write leaves nothing queued and the
+  /// caller can retry or keep its own copy.
+  pub fn push(&self, request: MainRequest) -> AppResult<()> {
+    let mut requests = self.requests.lock().unwrap();
+    let mut queued = requests.clone();
+    queued.push(request);
+    self.save(&queued)?;
+    *requests = queued;
+    Ok(())
+  }
+}
+
+pub fn queue_main_request(handle: &AppHandle, request: MainRequest) -> AppResult<()> {
+  handle.state::<MainRequestQueue>().push(request)?;
+  emit_main_requests_queued(handle)
+}
+
+/// Empties the queue; the persisted copy is cleared first, so a failed write leaves the requests queued instead of delivering them twice.
+pub fn take_main_requests(handle: &AppHandle) -> AppResult<Vec<MainRequest>> {
+  let queue = handle.state::<MainRequestQueue>();
+  let mut requests = queue.requests.lock().unwrap();
+  queue.save(&[])?;
+  Ok(std::mem::take(&mut *requests))
+}
+
+pub fn pomodoro_state(handle: &AppHandle) -> PomodoroState {
+  handle.state::<AppState>().state.lock().unwrap().clone()
+}
+
+pub fn set_task_name(handle: &AppHandle, name: Option<String>) -> AppResult<()> {
+  let state = handle.state::<AppState>();
+  let mut s = state.state.lock().unwrap();
+  s.current_task = name;
+  emit_timer(handle, TimerEvent::Tick(s.clone()))
+}
+
+pub fn pause(handle: &AppHandle) -> AppResult<()> {
+  let state = handle.state::<AppState>();
+  let mut s = state.state.lock().unwrap();
+  if !s.is_active {
+    return Ok(());
+  }
+  s.is_active = false;
+  emit_timer(handle, TimerEvent::Tick(s.clone()))
+}
+
+pub fn reset(handle: &AppHandle) -> AppResult<()> {
+  let state = handle.state::<AppState>();
+  let mut s = state.state.lock().unwrap();
+  s.is_active = false;
+  s.time_left = match s.mode.as_str() {
+    "shortBreak" => s.settings.short_break_duration.max(1) * 60,
+    "longBreak" => s.settings.long_break_duration.max(1) * 60,
+    _ => s.settings.work_duration.max(1) * 60,
+  };
+  emit_timer(handle, TimerEvent::Tick(s.clone()))
+}
+
+pub fn skip(handle: &AppHandle) -> AppResult<()> {
+  let state = handle.state::<AppState>();
+  let mut s = state.state.lock().unwrap();
+  let (next_mode, next_time_left) = next_mode_after_skip(&s.mode, s.sessions_completed, &s.settings);
+  s.mode = next_mode;
+  s.time_left = next_time_left;
+  s.is_active = false;
+  emit_timer(handle, TimerEvent::Tick(s.clone()))
+}
+
+/// Extends the current phase without changing whether it is running.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub fn add_time(handle: &AppHandle, seconds: u32) -> AppResult<()> {
+  let state = handle.state::<AppState>();
+  let mut s = state.state.lock().unwrap();
+  s.time_left = s.time_left.saturating_add(seconds);
+  emit_timer(handle, TimerEvent::Tick(s.clone()))
+}
+
+/// Starts (or restarts with a new length) a work session, optionally naming the task it is for.
+pub fn start_focus(handle: &AppHandle, task: Option<String>, minutes: Option<u32>) -> AppResult<()> {
+  let state = handle.state::<AppState>();
+  let mut s = state.state.lock().unwrap();
+  let was_running_work = s.is_active && s.mode == "work";
+  if let Some(task) = &task {
+    s.current_task = Some(task.clone());
+  }
+  if !was_running_work || minutes.is_some() {
+    s.mode = "work".to_string();
+    s.time_left = minutes.unwrap_or(s.settings.work_duration).max(1) * 60;
+  }
+  s.is_active = true;
+
+  let notified = if was_running_work { Ok(()) } else { show_system_notification(handle, "开始专注", "开始这一轮专注，保持节奏。") };
+  emit_timer(handle, TimerEvent::Tick(s.clone()))?;
+  drop(s);
+
+  if let Some(title) = task {
+    queue_main_request(handle, MainRequest::FocusTask { title })?;
+  }
+  notified
+}
+
+pub fn capture(handle: &AppHandle, text: String) -> AppResult<()> {
+  // Captured items are stored by the frontend, so a main window has to exist to receive them.
+  if handle.get_webview_window("main").is_none() {
+    perform_open_main(handle)?;
+  }
+  queue_main_request(handle, MainRequest::Capture { text })
+}
+
+/// Like `capture`, for items that come with notes.
+pub fn capture_note(handle: &AppHandle, title: String, notes: Option<String>) -> AppResult<()> {
+  if handle.get_webview_window("main").is_none() {
+    perform_open_main(handle)?;
+  }
+  queue_main_request(handle, MainRequest::ImportNote { title, notes })
+}
+
+/// Small always-on-top window for typing one Inbox entry; submitting it goes through `capture_text`.
+pub fn open_capture_window(handle: &AppHandle) -> AppResult<()> {
+  if let Some(window) = handle.get_webview_window("capture") {
+    window.show()?;
+    window.unminimize()?;
+    window.set_focus()?;
+  } else {
+    tauri::WebviewWindowBuilder::new(handle, "capture", tauri::WebviewUrl::App("/?view=capture".into()))
+      .title("Quick Capture")
+      .inner_size(520.0, 120.0)
+      .resizable(false)
+      .decorations(false)
+      .always_on_top(true)
+      .skip_taskbar(true)
+      .center()
+      .focused(true)
+      .build()?;
+  }
+  Ok(())
+}
+
+/// Hands text from the capture window to the Inbox without bringing up the main window; the
+/// request waits in the queue until the main window's store has loaded.
+pub fn capture_text(handle: &AppHandle, text: String) -> AppResult<()> {
+  let text = text.trim().to_string();
+  if !text.is_empty() {
+    queue_main_request(handle, MainRequest::Capture { text })?;
+  }
+  if let Some(window) = handle.get_webview_window("capture") {
+    window.close()?;
+  }
+  Ok(())
+}
+
+pub fn navigate(handle: &AppHandle, view: MainView) -> AppResult<()> {
+  perform_open_main(handle)?;
+  queue_main_request(handle, MainRequest::Navigate { view })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{MainRequest, MainRequestQueue, MainView};
+
+  #[test]
+  fn only_notes_and_captures_outlive_the_process() {
+    let path = std::env::temp_dir().join(format!("daily-planner-main-requests-{}.json", std::process::id()));
+    let queue = MainRequestQueue::load(path.clone());
+    let note = MainRequest::ImportNote { title: "Buy milk".to_string(), notes: None };
+    queue.save(&[MainRequest::Navigate { view: MainView::Review }, note.clone()]).expect("save queue");
+
+    assert_eq!(*MainRequestQueue::load(path.clone()).requests.lock().unwrap(), vec![note]);
+    let _ = std::fs::remove_file(path);
+  }
+}
@@ -0,0 +1,38 @@
+use crate::error::{AppError, AppResult};
+use crate::AppState;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_autostart::ManagerExt;
+
+/// Keeps the main window hidden at launch; the login item always passes it.
+pub const MINIMIZED_ARG: &str = "--minimized";
+
+pub fn launched_minimized<S: AsRef<str>>(args: &[S]) -> bool {
+  args.iter().any(|arg| arg.as_ref() == MINIMIZED_ARG)
+}
+
+/// Makes the login item (XDG autostart entry, LaunchAgent or Run key) match the `autostart` setting. Running it at
+/// every launch also rewrites the entry when an update moved the binary.
+pub fn sync(handle: &AppHandle) -> AppResult<()> {
+  let enabled = handle.state::<AppState>().desktop_settings.lock().unwrap().autostart.enabled;
+  let launcher = handle.autolaunch();
+  let result = if enabled {
+    launcher.enable()
+  } else if launcher.is_enabled().unwrap_or(true) {
+    launcher.disable()
+  } else {
+    Ok(())
+  };
+  result.map_err(|err| AppError::Io(format!("launch at login: {}", err)))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::launched_minimized;
+
+  #[test]
+  fn minimized_flag_is_found_among_other_arguments() {
+    assert!(launched_minimized(&["/usr/bin/daily-planner", "--minimized"]));
+    assert!(launched_minimized(&["--minimized", "daily-planner://open/today"]));
+    assert!(!launched_minimized(&["/usr/bin/daily-planner", "daily-planner://open/today"]));
+  }
+}
@@ -0,0 +1,70 @@
+use crate::events::{BackendError, DayRolledOver, FloatingEvent, MainRequestsQueued, SettingsEvent, TimerEvent};
+use specta_typescript::Typescript;
+use tauri_specta::{collect_commands, collect_events, Builder};
+
+/// Generated TypeScript for commands and events, committed so the frontend type-checks without a Rust build.
+/// Regenerate with `npm run bindings`.
+pub const BINDINGS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../src/bindings.ts");
+
+pub fn builder() -> Builder<tauri::Wry> {
+  Builder::<tauri::Wry>::new()
+    .commands(collect_commands![
+      crate::get_pomodoro_state,
+      crate::toggle_timer,
+      crate::reset_timer,
+      crate::skip_mode,
+      crate::update_settings,
+      crate::open_main,
+      crate::show_notification,
+      crate::toggle_floating_window,
+      crate::open_floating_mode,
+      crate::open_floating_settings,
+      crate::get_runtime_platform,
+      crate::broadcast_floating_preferences,
+      crate::update_task_name,
+      crate::set_floating_click_through,
+      crate::get_desktop_settings,
+      crate::update_desktop_settings,
+      crate::set_mqtt_password,
+      crate::open_capture_window,
+      crate::submit_capture,
+      crate::take_main_requests,
+      crate::roll_over_tasks,
+      crate::expand_recurrence,
+      crate::parse_recurrence_rule,
+      crate::load_legacy_daily_planner_ai_store,
+      crate::load_legacy_native_store_value,
+    ])
+    .events(collect_events![TimerEvent, SettingsEvent, FloatingEvent, BackendError, MainRequestsQueued, DayRolledOver])
+}
+
+pub fn typescript() -> Typescript {
+  Typescript::default().header("// @ts-nocheck\n/* eslint-disable */")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{builder, typescript, BINDINGS_PATH};
+  use std::fs;
+
+  /// Writes `src/bindings.ts`; ignored so only `npm run bindings` touches the source tree.
+  #[test]
+  #[ignore]
+  fn export_bindings() {
+    builder().export(typescript(), BINDINGS_PATH).expect("export bindings");
+  }
+
+  #[test]
+  fn committed_bindings_are_up_to_date() {
+    let generated = std::env::temp_dir().join(format!("daily-planner-bindings-{}.ts", std::process::id()));
+    builder().export(typescript(), &generated).expect("export bindings");
+    let expected = fs::read_to_string(&generated).expect("read generated bindings");
+    let _ = fs::remove_file(&generated);
+
+    let committed = fs::read_to_string(BINDINGS_PATH).expect("read committed bindings");
+    assert!(
+      committed == expected,
+      "src/bindings.ts is stale; run `npm run bindings` to regenerate it"
+    );
+  }
+}
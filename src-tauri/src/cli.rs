@@ -0,0 +1,218 @@
+use crate::actions;
+use crate::control;
+use crate::error::{AppError, AppResult};
+use crate::{perform_toggle_timer, PomodoroState};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+pub const USAGE: &str = "usage: daily-planner start [--task TEXT] [--minutes N] | pause | skip | status [--json] | add TEXT";
+
+/// A request sent by `daily-planner <command>` to the running app over the control socket.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(tag = "command", rename_all = "camelCase")]
+pub enum CliCommand {
+  Start { task: Option<String>, minutes: Option<u32> },
+  Pause,
+  Skip,
+  Status,
+  Add { text: String },
+  /// Not a CLI word; sent by the browser extension through the native messaging host.
+  Toggle,
+  /// Not a CLI word; the browser extension's page capture, with the URL kept as the item's notes.
+  Clip { title: String, url: Option<String> },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct CliInvocation {
+  pub command: CliCommand,
+  pub json: bool,
+}
+
+/// Returns `None` when `args` (without the program name) is not a CLI call, so the app starts normally.
+pub fn parse_cli_args(args: &[String]) -> Option<Result<CliInvocation, String>> {
+  let (name, rest) = args.split_first()?;
+  if !matches!(name.as_str(), "start" | "pause" | "skip" | "status" | "add") {
+    return None;
+  }
+  Some(parse_command(name, rest))
+}
+
+fn parse_command(name: &str, rest: &[String]) -> Result<CliInvocation, String> {
+  let mut json = false;
+  let mut task = None;
+  let mut minutes = None;
+  let mut words = Vec::new();
+  let mut args = rest.iter();
+
+  while let Some(arg) = args.next() {
+    match arg.as_str() {
+      "--json" => json = true,
+      "--task" if name == "start" => task = Some(args.next().ok_or("--task needs a value")?.clone()),
+      "--minutes" if name == "start" => {
+        let value = args.next().ok_or("--minutes needs a value")?;
+        let parsed = value.parse::<u32>().ok().filter(|minutes| (1..=600).contains(minutes));
+        minutes = Some(parsed.ok_or_else(|| format!("--minutes must be between 1 and 600, got {}", value))?);
+      }
+      word if name == "add" && !word.starts_with("--") => words.push(word),
+      other => return Err(format!("unexpected argument for {}: {}", name, other)),
+    }
+  }
+
+  let command = match name {
+    "start" => CliCommand::Start { task, minutes },
+    "pause" => CliCommand::Pause,
+    "skip" => CliCommand::Skip,
+    "status" => CliCommand::Status,
+    _ => {
+      let text = words.join(" ").trim().to_string();
+      if text.is_empty() {
+        return Err("add needs the text of the inbox item".to_string());
+      }
+      CliCommand::Add { text }
+    }
+  };
+  Ok(CliInvocation { command, json })
+}
+
+/// Runs inside the app for each control socket request; every command replies with the resulting timer state.
+pub fn execute(handle: &AppHandle, command: CliCommand) -> AppResult<PomodoroState> {
+  match command {
+    CliCommand::Start { task, minutes } => actions::start_focus(handle, task, minutes)?,
+    CliCommand::Pause => actions::pause(handle)?,
+    CliCommand::Skip => actions::skip(handle)?,
+    CliCommand::Status => {}
+    CliCommand::Add { text } => actions::capture(handle, text)?,
+    CliCommand::Toggle => perform_toggle_timer(handle)?,
+    CliCommand::Clip { title, url } => {
+      let url = url.map(|url| url.trim().to_string()).filter(|url| !url.is_empty());
+      let title = match title.trim() {
+        "" => url.clone().ok_or_else(|| AppError::Serialization("clip needs a title or a URL".to_string()))?,
+        title => title.to_string(),
+      };
+      actions::capture_note(handle, title, url)?
+    }
+  }
+  Ok(actions::pomodoro_state(handle))
+}
+
+/// One line, kept short for shell prompts and status bars.
+pub fn format_status(state: &PomodoroState) -> String {
+  let phase = match state.mode.as_str() {
+    "shortBreak" => "short break",
+    "longBreak" => "long break",
+    _ => "focus",
+  };
+  let mut line = format!("{} {:02}:{:02}", phase, state.time_left / 60, state.time_left % 60);
+  if !state.is_active {
+    line.push_str(" (paused)");
+  }
+  if let Some(task) = state.current_task.as_deref().filter(|task| !task.is_empty()) {
+    line.push_str(" · ");
+    line.push_str(task);
+  }
+  line
+}
+
+/// Client side: forwards the command to the running app and prints its reply. Returns the process exit code.
+pub fn run(invocation: CliInvocation) -> i32 {
+  #[cfg(target_os = "windows")]
+  attach_parent_console();
+
+  match control::request(&invocation.command) {
+    Ok(Ok(state)) if invocation.json => match serde_json::to_string(&state) {
+      Ok(json) => {
+        println!("{}", json);
+        0
+      }
+      Err(err) => {
+        eprintln!("daily-planner: {}", err);
+        1
+      }
+    },
+    Ok(Ok(state)) => {
+      println!("{}", format_status(&state));
+      0
+    }
+    Ok(Err(err)) => {
+      eprintln!("daily-planner: {}", err);
+      1
+    }
+    Err(err) => {
+      eprintln!("daily-planner: the app is not running ({})", err);
+      2
+    }
+  }
+}
+
+/// Release builds use the GUI subsystem on Windows, so output only shows up after attaching to the calling console.
+#[cfg(target_os = "windows")]
+fn attach_parent_console() {
+  use windows::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+  unsafe {
+    let _ = AttachConsole(ATTACH_PARENT_PROCESS);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{format_status, parse_cli_args, CliCommand, CliInvocation};
+  use crate::{PomodoroSettings, PomodoroState};
+
+  fn args(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+  }
+
+  #[test]
+  fn gui_launches_are_not_cli_calls() {
+    assert_eq!(parse_cli_args(&args(&[])), None);
+    assert_eq!(parse_cli_args(&args(&["daily-planner://open/today"])), None);
+    assert_eq!(parse_cli_args(&args(&["--minimized"])), None);
+  }
+
+  #[test]
+  fn start_accepts_task_and_minutes() {
+    assert_eq!(
+      parse_cli_args(&args(&["start", "--task", "Write report", "--minutes", "50"])),
+      Some(Ok(CliInvocation { command: CliCommand::Start { task: Some("Write report".to_string()), minutes: Some(50) }, json: false }))
+    );
+    assert!(matches!(parse_cli_args(&args(&["start", "--minutes", "0"])), Some(Err(_))));
+    assert!(matches!(parse_cli_args(&args(&["start", "--task"])), Some(Err(_))));
+  }
+
+  #[test]
+  fn add_joins_words_and_requires_text() {
+    assert_eq!(
+      parse_cli_args(&args(&["add", "call", "the bank"])),
+      Some(Ok(CliInvocation { command: CliCommand::Add { text: "call the bank".to_string() }, json: false }))
+    );
+    assert!(matches!(parse_cli_args(&args(&["add"])), Some(Err(_))));
+  }
+
+  #[test]
+  fn status_supports_json_and_rejects_unknown_flags() {
+    assert_eq!(
+      parse_cli_args(&args(&["status", "--json"])),
+      Some(Ok(CliInvocation { command: CliCommand::Status, json: true }))
+    );
+    assert!(matches!(parse_cli_args(&args(&["pause", "--task", "x"])), Some(Err(_))));
+  }
+
+  #[test]
+  fn status_line_mentions_pause_and_task() {
+    let mut state = PomodoroState {
+      time_left: 24 * 60 + 13,
+      is_active: true,
+      mode: "work".to_string(),
+      sessions_completed: 0,
+      last_date: "2026-01-01".to_string(),
+      settings: PomodoroSettings::default(),
+      current_task: Some("Write report".to_string()),
+    };
+    assert_eq!(format_status(&state), "focus 24:13 · Write report");
+
+    state.is_active = false;
+    state.mode = "longBreak".to_string();
+    state.current_task = None;
+    assert_eq!(format_status(&state), "long break 24:13 (paused)");
+  }
+}
@@ -0,0 +1,198 @@
+use crate::cli::{self, CliCommand};
+use crate::error::{AppError, AppResult};
+use crate::events::report_error;
+use crate::PomodoroState;
+use std::io::{self, BufRead, BufReader, Write};
+#[cfg(unix)]
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt};
+
+/// A reply slower than this means the app is wedged; shell prompts should not hang on it.
+#[cfg(unix)]
+const CLIENT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// `XDG_RUNTIME_DIR`, or else a directory of this user's own in the shared temp dir, named by uid so it cannot be
+/// confused with another user's. `prepare_runtime_dir` creates it.
+#[cfg(unix)]
+fn runtime_dir() -> PathBuf {
+  std::env::var_os("XDG_RUNTIME_DIR")
+    .map(PathBuf::from)
+    .unwrap_or_else(|| std::env::temp_dir().join(format!("daily-planner-{}", unsafe { libc::getuid() })))
+}
+
+/// Per-user endpoint of the control socket; the CLI computes it without starting Tauri.
+#[cfg(unix)]
+pub fn socket_path() -> PathBuf {
+  let user = std::env::var("USER").unwrap_or_else(|_| "user".to_string());
+  runtime_dir().join(format!("daily-planner-{}.sock", user))
+}
+
+#[cfg(unix)]
+fn check_owner(path: &Path, metadata: &std::fs::Metadata) -> io::Result<()> {
+  use std::os::unix::fs::MetadataExt;
+
+  if metadata.uid() != unsafe { libc::getuid() } {
+    return Err(io::Error::new(io::ErrorKind::PermissionDenied, format!("{} belongs to another user", path.display())));
+  }
+  Ok(())
+}
+
+/// Creates `dir` with mode 0700, or checks that the existing one is a directory only this user can enter, so nobody
+/// else can claim the names in it or connect to its sockets.
+#[cfg(unix)]
+fn prepare_dir(dir: &Path) -> io::Result<()> {
+  use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+  match std::fs::DirBuilder::new().mode(0o700).create(dir) {
+    Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {
+      let metadata = std::fs::symlink_metadata(dir)?;
+      check_owner(dir, &metadata)?;
+      if !metadata.is_dir() || metadata.permissions().mode() & 0o077 != 0 {
+        return Err(io::Error::new(io::ErrorKind::PermissionDenied, format!("{} is not a private directory", dir.display())));
+      }
+      Ok(())
+    }
+    result => result,
+  }
+}
+
+/// The directory of the sockets and the default status bar output, ready for use.
+#[cfg(unix)]
+pub fn prepare_runtime_dir() -> io::Result<PathBuf> {
+  let dir = runtime_dir();
+  prepare_dir(&dir)?;
+  Ok(dir)
+}
+
+/// Binds a socket that only this user can reach. A socket left behind by an earlier run of ours is replaced; a path
+/// that belongs to anyone else is an error instead.
+#[cfg(unix)]
+pub fn bind_private(path: &Path) -> io::Result<tokio::net::UnixListener> {
+  use std::os::unix::fs::PermissionsExt;
+
+  if let Some(dir) = path.parent() {
+    prepare_dir(dir)?;
+  }
+  match std::fs::symlink_metadata(path) {
+    Ok(metadata) => {
+      check_owner(path, &metadata)?;
+      std::fs::remove_file(path)?;
+    }
+    Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+    Err(err) => return Err(err),
+  }
+  let listener = tokio::net::UnixListener::bind(path)?;
+  std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+  Ok(listener)
+}
+
+#[cfg(windows)]
+pub fn pipe_name() -> String {
+  let user = std::env::var("USERNAME").unwrap_or_else(|_| "user".to_string());
+  format!(r"\\.\pipe\daily-planner-{}", user)
+}
+
+/// Sends one command to the running app and waits for its reply.
+pub fn request(command: &CliCommand) -> io::Result<AppResult<PomodoroState>> {
+  #[cfg(unix)]
+  let stream = {
+    let path = socket_path();
+    // Talks only to a socket in this user's own directory, never to one another user planted.
+    if let Some(dir) = path.parent() {
+      check_owner(dir, &std::fs::symlink_metadata(dir)?)?;
+    }
+    let stream = std::os::unix::net::UnixStream::connect(&path)?;
+    stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+    stream.set_write_timeout(Some(CLIENT_TIMEOUT))?;
+    stream
+  };
+  #[cfg(windows)]
+  let stream = std::fs::OpenOptions::new().read(true).write(true).open(pipe_name())?;
+
+  let mut writer = &stream;
+  writer.write_all(format!("{}\n", serde_json::to_string(command)?).as_bytes())?;
+  writer.flush()?;
+
+  let mut line = String::new();
+  BufReader::new(&stream).read_line(&mut line)?;
+  Ok(serde_json::from_str(&line)?)
+}
+
+async fn serve_connection<S: AsyncRead + AsyncWrite + Unpin>(handle: &AppHandle, stream: S) -> io::Result<()> {
+  let (reader, mut writer) = tokio::io::split(stream);
+  let mut line = String::new();
+  tokio::io::BufReader::new(reader).read_line(&mut line).await?;
+
+  let reply = match serde_json::from_str::<CliCommand>(&line) {
+    Ok(command) => cli::execute(handle, command),
+    Err(err) => Err(AppError::from(err)),
+  };
+  writer.write_all(format!("{}\n", serde_json::to_string(&reply)?).as_bytes()).await?;
+  writer.flush().await
+}
+
+#[cfg(unix)]
+async fn listen(handle: AppHandle) -> io::Result<()> {
+  // The single-instance plugin guarantees no other copy is serving, so an existing socket of ours is stale.
+  let listener = bind_private(&socket_path())?;
+
+  loop {
+    let (stream, _) = listener.accept().await?;
+    let handle = handle.clone();
+    tauri::async_runtime::spawn(async move {
+      let _ = serve_connection(&handle, stream).await;
+    });
+  }
+}
+
+#[cfg(windows)]
+async fn listen(handle: AppHandle) -> io::Result<()> {
+  use tokio::net::windows::named_pipe::ServerOptions;
+
+  let name = pipe_name();
+  let mut server = ServerOptions::new().first_pipe_instance(true).create(&name)?;
+  loop {
+    server.connect().await?;
+    let connected = std::mem::replace(&mut server, ServerOptions::new().create(&name)?);
+    let handle = handle.clone();
+    tauri::async_runtime::spawn(async move {
+      let _ = serve_connection(&handle, connected).await;
+    });
+  }
+}
+
+pub fn spawn_server(handle: &AppHandle) {
+  let handle = handle.clone();
+  tauri::async_runtime::spawn(async move {
+    let result = listen(handle.clone()).await.map_err(AppError::from);
+    report_error(&handle, "command-line control socket", result);
+  });
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+  use super::{bind_private, prepare_dir};
+  use std::os::unix::fs::PermissionsExt;
+
+  #[test]
+  fn sockets_live_in_a_private_directory() {
+    let dir = std::env::temp_dir().join(format!("daily-planner-control-test-{}", std::process::id()));
+    prepare_dir(&dir).expect("create directory");
+    assert_eq!(std::fs::metadata(&dir).unwrap().permissions().mode() & 0o777, 0o700);
+    prepare_dir(&dir).expect("reuse own directory");
+
+    let path = dir.join("control.sock");
+    let runtime = tokio::runtime::Runtime::new().expect("runtime");
+    runtime.block_on(async {
+      drop(bind_private(&path).expect("bind"));
+      // A socket left behind by an earlier run is replaced.
+      drop(bind_private(&path).expect("bind over stale socket"));
+    });
+    assert_eq!(std::fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+
+    std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o755)).unwrap();
+    assert_eq!(prepare_dir(&dir).unwrap_err().kind(), std::io::ErrorKind::PermissionDenied);
+    let _ = std::fs::remove_dir_all(dir);
+  }
+}
@@ -0,0 +1,54 @@
+use chrono::{DateTime, Duration, Local, NaiveDate, TimeZone};
+
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// The planner's date at `now`: until `start_hour` (0 to 23, see `update_desktop_settings`) the previous calendar day is still running.
+pub fn logical_date<Tz: TimeZone>(now: &DateTime<Tz>, start_hour: u32) -> NaiveDate {
+  (now.naive_local() - Duration::hours(i64::from(start_hour))).date()
+}
+
+/// `YYYY-MM-DD` of the current planner day. `Local` follows the system time zone, also when it changes while running.
+pub fn today(start_hour: u32) -> String {
+  logical_date(&Local::now(), start_hour).format(DATE_FORMAT).to_string()
+}
+
+/// Only a later date starts a new day, so a time-zone change that moves the clock back does not reset the count.
+pub fn has_rolled_over(last_date: &str, today: &str) -> bool {
+  match (NaiveDate::parse_from_str(last_date, DATE_FORMAT), NaiveDate::parse_from_str(today, DATE_FORMAT)) {
+    (Ok(last), Ok(today)) => today > last,
+    _ => last_date != today,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{has_rolled_over, logical_date};
+  use chrono::{FixedOffset, NaiveDate, TimeZone};
+
+  #[test]
+  fn hours_before_the_start_belong_to_the_previous_day() {
+    let zone = FixedOffset::east_opt(8 * 3600).unwrap();
+    let late = zone.with_ymd_and_hms(2026, 3, 10, 2, 30, 0).unwrap();
+    assert_eq!(logical_date(&late, 0), NaiveDate::from_ymd_opt(2026, 3, 10).unwrap());
+    assert_eq!(logical_date(&late, 4), NaiveDate::from_ymd_opt(2026, 3, 9).unwrap());
+    let morning = zone.with_ymd_and_hms(2026, 3, 10, 4, 0, 0).unwrap();
+    assert_eq!(logical_date(&morning, 4), NaiveDate::from_ymd_opt(2026, 3, 10).unwrap());
+  }
+
+  #[test]
+  fn the_local_clock_decides_after_a_time_zone_change() {
+    // The same instant is already the 10th in Shanghai but still the 9th in Berlin.
+    let instant = FixedOffset::east_opt(8 * 3600).unwrap().with_ymd_and_hms(2026, 3, 10, 6, 0, 0).unwrap();
+    let berlin = instant.with_timezone(&FixedOffset::east_opt(3600).unwrap());
+    assert_eq!(logical_date(&instant, 4), NaiveDate::from_ymd_opt(2026, 3, 10).unwrap());
+    assert_eq!(logical_date(&berlin, 4), NaiveDate::from_ymd_opt(2026, 3, 9).unwrap());
+  }
+
+  #[test]
+  fn flying_west_does_not_start_a_new_day() {
+    assert!(has_rolled_over("2026-03-09", "2026-03-10"));
+    assert!(!has_rolled_over("2026-03-10", "2026-03-09"));
+    assert!(!has_rolled_over("2026-03-10", "2026-03-10"));
+    assert!(has_rolled_over("", "2026-03-10"));
+  }
+}
@@ -0,0 +1,329 @@
+use crate::actions;
+use crate::error::{AppError, AppResult};
+use crate::events::{report_error, TimerBus, TimerEvent};
+use crate::{perform_toggle_timer, PomodoroState};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+use tokio::sync::broadcast;
+use zbus::connection::{Builder, Connection};
+
+pub const BUS_NAME: &str = "com.lqf0624.dailyplanner";
+pub const OBJECT_PATH: &str = "/com/lqf0624/dailyplanner/Timer";
+
+/// What the D-Bus object drives; the app implements it on `AppHandle`, tests on a plain state.
+pub trait TimerBackend: Send + Sync + 'static {
+  fn state(&self) -> PomodoroState;
+  fn toggle(&self) -> AppResult<()>;
+  fn skip(&self) -> AppResult<()>;
+  fn reset(&self) -> AppResult<()>;
+  fn add_time(&self, seconds: u32) -> AppResult<()>;
+}
+
+impl TimerBackend for AppHandle {
+  fn state(&self) -> PomodoroState {
+    actions::pomodoro_state(self)
+  }
+
+  fn toggle(&self) -> AppResult<()> {
+    perform_toggle_timer(self)
+  }
+
+  fn skip(&self) -> AppResult<()> {
+    actions::skip(self)
+  }
+
+  fn reset(&self) -> AppResult<()> {
+    actions::reset(self)
+  }
+
+  fn add_time(&self, seconds: u32) -> AppResult<()> {
+    actions::add_time(self, seconds)
+  }
+}
+
+struct TimerObject {
+  backend: Arc<dyn TimerBackend>,
+}
+
+fn failed(err: AppError) -> zbus::fdo::Error {
+  zbus::fdo::Error::Failed(err.to_string())
+}
+
+#[zbus::interface(name = "com.lqf0624.dailyplanner.Timer")]
+impl TimerObject {
+  fn toggle(&self) -> zbus::fdo::Result<()> {
+    self.backend.toggle().map_err(failed)
+  }
+
+  fn skip(&self) -> zbus::fdo::Result<()> {
+    self.backend.skip().map_err(failed)
+  }
+
+  fn reset(&self) -> zbus::fdo::Result<()> {
+    self.backend.reset().map_err(failed)
+  }
+
+  /// Extends the current phase by `seconds`.
+  fn add_time(&self, seconds: u32) -> zbus::fdo::Result<()> {
+    self.backend.add_time(seconds).map_err(failed)
+  }
+
+  /// `work`, `shortBreak` or `longBreak`.
+  #[zbus(property)]
+  fn mode(&self) -> String {
+    self.backend.state().mode
+  }
+
+  /// Seconds left in the current phase.
+  #[zbus(property)]
+  fn time_left(&self) -> u32 {
+    self.backend.state().time_left
+  }
+
+  #[zbus(property)]
+  fn is_active(&self) -> bool {
+    self.backend.state().is_active
+  }
+
+  /// Empty when no task is bound; D-Bus has no null.
+  #[zbus(property)]
+  fn current_task(&self) -> String {
+    self.backend.state().current_task.unwrap_or_default()
+  }
+
+  #[zbus(property)]
+  fn sessions_completed(&self) -> u32 {
+    self.backend.state().sessions_completed
+  }
+}
+
+/// Claims the bus name and exports the timer object on the connection `builder` describes.
+pub async fn serve(builder: Builder<'_>, backend: Arc<dyn TimerBackend>) -> zbus::Result<Connection> {
+  builder.name(BUS_NAME)?.serve_at(OBJECT_PATH, TimerObject { backend })?.build().await
+}
+
+/// Emits `PropertiesChanged` for each property that differs between the two states.
+pub async fn publish_changes(connection: &Connection, before: &PomodoroState, after: &PomodoroState) -> zbus::Result<()> {
+  let object = connection.object_server().interface::<_, TimerObject>(OBJECT_PATH).await?;
+  let emitter = object.signal_emitter();
+  let timer = object.get().await;
+  if before.mode != after.mode {
+    timer.mode_changed(emitter).await?;
+  }
+  if before.time_left != after.time_left {
+    timer.time_left_changed(emitter).await?;
+  }
+  if before.is_active != after.is_active {
+    timer.is_active_changed(emitter).await?;
+  }
+  if before.current_task != after.current_task {
+    timer.current_task_changed(emitter).await?;
+  }
+  if before.sessions_completed != after.sessions_completed {
+    timer.sessions_completed_changed(emitter).await?;
+  }
+  Ok(())
+}
+
+/// Publishes the changes behind every timer event. Only ticks carry the state; after the others (a finished session, a
+/// reached goal) and after missed events it is read from `backend`, so no change waits for the next tick.
+async fn forward(connection: &Connection, backend: &dyn TimerBackend, mut events: broadcast::Receiver<TimerEvent>) -> zbus::Result<()> {
+  let mut last = backend.state();
+  loop {
+    let state = match events.recv().await {
+      Ok(TimerEvent::Tick(state)) => state,
+      Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => backend.state(),
+      Err(broadcast::error::RecvError::Closed) => return Ok(()),
+    };
+    publish_changes(connection, &last, &state).await?;
+    last = state;
+  }
+}
+
+async fn run(handle: AppHandle, events: broadcast::Receiver<TimerEvent>) -> zbus::Result<()> {
+  let connection = serve(Builder::session()?, Arc::new(handle.clone())).await?;
+  forward(&connection, &handle, events).await
+}
+
+pub fn spawn_service(handle: &AppHandle) {
+  let handle = handle.clone();
+  let events = handle.state::<TimerBus>().subscribe();
+  tauri::async_runtime::spawn(async move {
+    let result = run(handle.clone(), events).await.map_err(|err| AppError::Io(err.to_string()));
+    report_error(&handle, "D-Bus service", result);
+  });
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{forward, publish_changes, serve, TimerBackend, BUS_NAME, OBJECT_PATH};
+  use crate::error::AppResult;
+  use crate::events::TimerEvent;
+  use crate::{PomodoroSettings, PomodoroState};
+  use std::collections::HashSet;
+  use std::io::{BufRead, BufReader};
+  use std::process::{Child, Command, Stdio};
+  use std::sync::{Arc, Mutex};
+  use zbus::proxy::CacheProperties;
+
+  #[zbus::proxy(interface = "com.lqf0624.dailyplanner.Timer", default_service = "com.lqf0624.dailyplanner", default_path = "/com/lqf0624/dailyplanner/Timer")]
+  trait Timer {
+    fn toggle(&self) -> zbus::Result<()>;
+    fn add_time(&self, seconds: u32) -> zbus::Result<()>;
+    #[zbus(property)]
+    fn mode(&self) -> zbus::Result<String>;
+    #[zbus(property)]
+    fn time_left(&self) -> zbus::Result<u32>;
+    #[zbus(property)]
+    fn is_active(&self) -> zbus::Result<bool>;
+    #[zbus(property)]
+    fn current_task(&self) -> zbus::Result<String>;
+  }
+
+  struct FakeTimer(Mutex<PomodoroState>);
+
+  impl TimerBackend for FakeTimer {
+    fn state(&self) -> PomodoroState {
+      self.0.lock().unwrap().clone()
+    }
+
+    fn toggle(&self) -> AppResult<()> {
+      let mut state = self.0.lock().unwrap();
+      state.is_active = !state.is_active;
+      Ok(())
+    }
+
+    fn skip(&self) -> AppResult<()> {
+      Ok(())
+    }
+
+    fn reset(&self) -> AppResult<()> {
+      Ok(())
+    }
+
+    fn add_time(&self, seconds: u32) -> AppResult<()> {
+      self.0.lock().unwrap().time_left += seconds;
+      Ok(())
+    }
+  }
+
+  /// A throwaway `dbus-daemon`, so the test never touches the developer's session bus.
+  struct PrivateBus {
+    daemon: Child,
+    address: String,
+  }
+
+  impl PrivateBus {
+    fn start() -> Self {
+      let mut daemon = Command::new("dbus-daemon")
+        .args(["--session", "--nofork", "--print-address"])
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("start dbus-daemon (install the dbus package)");
+      let mut address = String::new();
+      BufReader::new(daemon.stdout.take().expect("dbus-daemon stdout")).read_line(&mut address).expect("read bus address");
+      Self { daemon, address: address.trim().to_string() }
+    }
+  }
+
+  impl Drop for PrivateBus {
+    fn drop(&mut self) {
+      let _ = self.daemon.kill();
+      let _ = self.daemon.wait();
+    }
+  }
+
+  fn paused_work() -> Arc<FakeTimer> {
+    Arc::new(FakeTimer(Mutex::new(PomodoroState {
+      time_left: 300,
+      is_active: false,
+      mode: "work".to_string(),
+      sessions_completed: 2,
+      last_date: "2026-01-01".to_string(),
+      settings: PomodoroSettings::default(),
+      current_task: None,
+    })))
+  }
+
+  fn properties_proxy(client: &zbus::blocking::Connection) -> zbus::blocking::fdo::PropertiesProxy<'static> {
+    zbus::blocking::fdo::PropertiesProxy::builder(client)
+      .destination(BUS_NAME)
+      .unwrap()
+      .path(OBJECT_PATH)
+      .unwrap()
+      .build()
+      .expect("properties proxy")
+  }
+
+  /// Run by the `integration` CI job: `cargo test -- --ignored dbus::`.
+  #[test]
+  #[ignore = "needs dbus-daemon"]
+  fn exports_properties_methods_and_change_signals() {
+    let bus = PrivateBus::start();
+    let backend = paused_work();
+    let runtime = tokio::runtime::Runtime::new().expect("runtime");
+    let server = runtime
+      .block_on(serve(zbus::connection::Builder::address(bus.address.as_str()).expect("address"), backend.clone()))
+      .expect("serve timer object");
+
+    let client = zbus::blocking::connection::Builder::address(bus.address.as_str()).expect("address").build().expect("connect");
+    let timer = TimerProxyBlocking::builder(&client).cache_properties(CacheProperties::No).build().expect("proxy");
+    assert_eq!(timer.time_left().unwrap(), 300);
+    assert_eq!(timer.current_task().unwrap(), "");
+
+    let properties = properties_proxy(&client);
+    let signals = properties.receive_properties_changed().expect("subscribe");
+
+    let before = backend.state();
+    timer.toggle().unwrap();
+    timer.add_time(60).unwrap();
+    assert!(timer.is_active().unwrap());
+    assert_eq!(timer.time_left().unwrap(), 360);
+    runtime.block_on(publish_changes(&server, &before, &backend.state())).expect("publish");
+
+    let changed = signals
+      .take(2)
+      .flat_map(|signal| signal.args().unwrap().changed_properties().keys().map(|name| name.to_string()).collect::<Vec<_>>())
+      .collect::<HashSet<_>>();
+    assert_eq!(changed, HashSet::from(["TimeLeft".to_string(), "IsActive".to_string()]));
+  }
+
+  #[test]
+  #[ignore = "needs dbus-daemon"]
+  fn events_without_state_still_signal_changes() {
+    let bus = PrivateBus::start();
+    let backend = paused_work();
+    let runtime = tokio::runtime::Runtime::new().expect("runtime");
+    let server = runtime
+      .block_on(serve(zbus::connection::Builder::address(bus.address.as_str()).expect("address"), backend.clone()))
+      .expect("serve timer object");
+    let (events, receiver) = tokio::sync::broadcast::channel(8);
+    let forwarding = {
+      let backend = backend.clone();
+      runtime.spawn(async move { forward(&server, &*backend, receiver).await })
+    };
+
+    let client = zbus::blocking::connection::Builder::address(bus.address.as_str()).expect("address").build().expect("connect");
+    let properties = properties_proxy(&client);
+    let signals = properties.receive_properties_changed().expect("subscribe");
+
+    // A finished session while the timer stays paused: no tick follows, the event alone has to announce it.
+    {
+      let mut state = backend.0.lock().unwrap();
+      state.mode = "shortBreak".to_string();
+      state.sessions_completed += 1;
+    }
+    events.send(TimerEvent::WorkCompleted { work_duration: 25 }).expect("send");
+
+    let changed = signals
+      .take(2)
+      .flat_map(|signal| signal.args().unwrap().changed_properties().keys().map(|name| name.to_string()).collect::<Vec<_>>())
+      .collect::<HashSet<_>>();
+    assert_eq!(changed, HashSet::from(["Mode".to_string(), "SessionsCompleted".to_string()]));
+    let timer = TimerProxyBlocking::builder(&client).cache_properties(CacheProperties::No).build().expect("proxy");
+    assert_eq!(timer.mode().unwrap(), "shortBreak");
+
+    drop(events);
+    runtime.block_on(forwarding).unwrap().expect("forwarding ends with the bus");
+  }
+}
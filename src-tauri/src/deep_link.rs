@@ -0,0 +1,123 @@
+use crate::actions::{self, MainView};
+use crate::error::AppResult;
+use crate::events::report_error;
+use tauri::AppHandle;
+
+pub const SCHEME: &str = "daily-planner";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DeepLink {
+  Start { task: Option<String>, minutes: Option<u32> },
+  /// Without text this opens the quick-capture window.
+  Capture { text: Option<String> },
+  Open { view: MainView },
+}
+
+fn query_value(query: &str, key: &str) -> Option<String> {
+  query
+    .split('&')
+    .filter_map(|pair| pair.split_once('=').or(Some((pair, ""))))
+    .find(|(name, _)| *name == key)
+    .and_then(|(_, value)| urlencoding::decode(&value.replace('+', " ")).ok().map(|value| value.trim().to_string()))
+    .filter(|value| !value.is_empty())
+}
+
+/// Parses `daily-planner://start?task=..&minutes=..`, `://capture[?text=..]` and `://open/<view>`.
+pub fn parse_deep_link(url: &str) -> Option<DeepLink> {
+  let rest = url.trim().strip_prefix(SCHEME)?.strip_prefix("://")?;
+  let rest = rest.split('#').next().unwrap_or_default();
+  let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+  let mut segments = path.split('/').filter(|segment| !segment.is_empty());
+
+  match segments.next()? {
+    "start" => Some(DeepLink::Start {
+      task: query_value(query, "task"),
+      minutes: query_value(query, "minutes").and_then(|minutes| minutes.parse::<u32>().ok()).filter(|minutes| (1..=600).contains(minutes)),
+    }),
+    "capture" => Some(DeepLink::Capture { text: query_value(query, "text") }),
+    "open" => segments.next().and_then(MainView::from_name).map(|view| DeepLink::Open { view }),
+    _ => None,
+  }
+}
+
+/// Command lines may carry other flags; the first argument using our scheme wins.
+pub fn find_deep_link<I, S>(args: I) -> Option<DeepLink>
+where
+  I: IntoIterator<Item = S>,
+  S: AsRef<str>,
+{
+  args.into_iter().find_map(|arg| parse_deep_link(arg.as_ref()))
+}
+
+pub fn dispatch(handle: &AppHandle, link: DeepLink) -> AppResult<()> {
+  match link {
+    DeepLink::Start { task, minutes } => actions::start_focus(handle, task, minutes),
+    DeepLink::Capture { text: Some(text) } => actions::capture(handle, text),
+    DeepLink::Capture { text: None } => actions::open_capture_window(handle),
+    DeepLink::Open { view } => actions::navigate(handle, view),
+  }
+}
+
+/// Handles a deep link in `args`, returning `false` when there was none.
+pub fn handle_args<I, S>(handle: &AppHandle, args: I) -> bool
+where
+  I: IntoIterator<Item = S>,
+  S: AsRef<str>,
+{
+  let Some(link) = find_deep_link(args) else { return false; };
+  report_error(handle, "deep link", dispatch(handle, link));
+  true
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{find_deep_link, parse_deep_link, DeepLink};
+  use crate::actions::MainView;
+
+  #[test]
+  fn start_reads_task_and_minutes() {
+    assert_eq!(
+      parse_deep_link("daily-planner://start?task=Write%20report&minutes=50"),
+      Some(DeepLink::Start { task: Some("Write report".to_string()), minutes: Some(50) })
+    );
+    assert_eq!(parse_deep_link("daily-planner://start"), Some(DeepLink::Start { task: None, minutes: None }));
+  }
+
+  #[test]
+  fn start_ignores_out_of_range_minutes() {
+    assert_eq!(parse_deep_link("daily-planner://start?minutes=0"), Some(DeepLink::Start { task: None, minutes: None }));
+    assert_eq!(parse_deep_link("daily-planner://start/?minutes=abc&task="), Some(DeepLink::Start { task: None, minutes: None }));
+  }
+
+  #[test]
+  fn capture_without_text_opens_the_capture_window() {
+    assert_eq!(
+      parse_deep_link("daily-planner://capture?text=Buy+milk"),
+      Some(DeepLink::Capture { text: Some("Buy milk".to_string()) })
+    );
+    assert_eq!(parse_deep_link("daily-planner://capture?text=%20"), Some(DeepLink::Capture { text: None }));
+    assert_eq!(parse_deep_link("daily-planner://capture"), Some(DeepLink::Capture { text: None }));
+  }
+
+  #[test]
+  fn open_accepts_known_views_only() {
+    assert_eq!(parse_deep_link("daily-planner://open/today"), Some(DeepLink::Open { view: MainView::Today }));
+    assert_eq!(parse_deep_link("daily-planner://open/review/"), Some(DeepLink::Open { view: MainView::Review }));
+    assert_eq!(parse_deep_link("daily-planner://open/calendar"), Some(DeepLink::Open { view: MainView::Calendar }));
+    assert_eq!(parse_deep_link("daily-planner://open/settings"), None);
+  }
+
+  #[test]
+  fn other_schemes_and_hosts_are_rejected() {
+    assert_eq!(parse_deep_link("https://start?task=x"), None);
+    assert_eq!(parse_deep_link("daily-planner:start"), None);
+    assert_eq!(parse_deep_link("daily-planner://delete-everything"), None);
+  }
+
+  #[test]
+  fn argv_is_searched_for_the_first_link() {
+    let args = ["/usr/bin/daily-planner", "--minimized", "daily-planner://open/inbox", "daily-planner://open/today"];
+    assert_eq!(find_deep_link(args), Some(DeepLink::Open { view: MainView::Inbox }));
+    assert_eq!(find_deep_link(["/usr/bin/daily-planner"]), None);
+  }
+}
@@ -0,0 +1,313 @@
+use crate::error::AppResult;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Clone, Copy, Serialize, Deserialize, Type, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TrayClickAction {
+  None,
+  ToggleTimer,
+  OpenMain,
+  OpenFloating,
+}
+
+/// Tauri reports no tray icon clicks on Linux, so the click actions have no effect there; the tray menu opens instead.
+#[derive(Clone, Serialize, Deserialize, Type, Debug)]
+#[serde(default)]
+pub struct TraySettings {
+  pub single_click: TrayClickAction,
+  pub double_click: TrayClickAction,
+  pub middle_click: TrayClickAction,
+  /// Shows the countdown next to the tray icon (menu bar title on macOS, indicator label on Linux).
+  pub show_countdown: bool,
+}
+
+impl Default for TraySettings {
+  fn default() -> Self {
+    Self {
+      single_click: TrayClickAction::None,
+      double_click: TrayClickAction::OpenMain,
+      middle_click: TrayClickAction::None,
+      show_countdown: true,
+    }
+  }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Type, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum BarEdge {
+  Top,
+  Bottom,
+  Left,
+  Right,
+}
+
+#[derive(Clone, Serialize, Deserialize, Type, Debug)]
+#[serde(default)]
+pub struct FloatingSettings {
+  pub snap_to_edges: bool,
+  /// Logical pixels from a work-area edge within which the floating window snaps to it.
+  pub snap_distance: u32,
+  /// Native window opacity, kept in sync with the floating settings view.
+  pub opacity: f64,
+  pub hide_during_breaks: bool,
+  pub show_on_work_start: bool,
+  pub hide_when_main_focused: bool,
+  /// Screen edge the `bar` floating mode docks to.
+  pub bar_edge: BarEdge,
+  /// Bar thickness in logical pixels.
+  pub bar_thickness: u32,
+}
+
+impl Default for FloatingSettings {
+  fn default() -> Self {
+    Self {
+      snap_to_edges: false,
+      snap_distance: 16,
+      opacity: 0.96,
+      hide_during_breaks: false,
+      show_on_work_start: false,
+      hide_when_main_focused: false,
+      bar_edge: BarEdge::Top,
+      bar_thickness: 6,
+    }
+  }
+}
+
+#[derive(Clone, Serialize, Deserialize, Type, Debug, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct RpcSettings {
+  /// Off by default, since the server lets any local process of this user drive the timer.
+  pub enabled: bool,
+  /// Localhost TCP port on platforms without the Unix socket; 0 picks a free one. The port and token are written to `rpc_endpoint.json`.
+  pub port: u16,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Type, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum StatusBarFormat {
+  /// One JSON object per update with `text`, `tooltip`, `class` and `percentage`, for `return-type: json`.
+  Waybar,
+  Polybar,
+  I3blocks,
+}
+
+/// Timer output for Linux status bars, for setups where the tray indicator is missing or broken.
+#[derive(Clone, Serialize, Deserialize, Type, Debug, PartialEq, Eq)]
+#[serde(default)]
+pub struct StatusBarSettings {
+  pub enabled: bool,
+  pub format: StatusBarFormat,
+  /// Output path; empty uses `daily-planner-<user>.status` next to the control socket.
+  pub path: String,
+  /// Streams one line per update into a FIFO (for `exec`/`tail`/`persist` modules) instead of rewriting a file.
+  pub fifo: bool,
+}
+
+impl Default for StatusBarSettings {
+  fn default() -> Self {
+    Self { enabled: false, format: StatusBarFormat::Waybar, path: String::new(), fifo: false }
+  }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Type, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum HookEvent {
+  /// The timer starts or resumes running a work session.
+  WorkStarted,
+  WorkFinished,
+  /// The timer starts or resumes running a short or long break.
+  BreakStarted,
+  BreakFinished,
+  GoalReached,
+}
+
+#[derive(Clone, Serialize, Deserialize, Type, Debug, PartialEq, Eq)]
+pub struct ShellHook {
+  pub event: HookEvent,
+  /// Run through `sh -c` (`cmd /C` on Windows) with `DAILY_PLANNER_*` variables set and the event as JSON on stdin.
+  pub command: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Type, Debug, PartialEq, Eq)]
+#[serde(default)]
+pub struct HookSettings {
+  pub commands: Vec<ShellHook>,
+  /// Seconds after which a hook that is still running gets killed.
+  pub timeout_secs: u32,
+}
+
+impl Default for HookSettings {
+  fn default() -> Self {
+    Self { commands: Vec::new(), timeout_secs: 10 }
+  }
+}
+
+#[derive(Clone, Serialize, Deserialize, Type, Debug, PartialEq, Eq)]
+pub struct Webhook {
+  pub url: String,
+  /// Transitions that are posted to `url`; empty posts all of them.
+  #[serde(default)]
+  pub events: Vec<HookEvent>,
+}
+
+/// Broker connection for publishing the timer to home automation (for example Home Assistant).
+#[derive(Clone, Serialize, Deserialize, Type, Debug, PartialEq, Eq)]
+#[serde(default)]
+pub struct MqttSettings {
+  pub enabled: bool,
+  pub host: String,
+  pub port: u16,
+  /// Left empty for brokers that allow anonymous clients. The password is kept in the OS keyring (`set_mqtt_password`).
+  pub username: String,
+  /// Topic segment in `daily-planner/<profile>/...`, so several machines can share a broker.
+  pub profile: String,
+  /// Subscribes to `daily-planner/<profile>/set` and runs the commands published there.
+  pub accept_commands: bool,
+}
+
+impl Default for MqttSettings {
+  fn default() -> Self {
+    Self {
+      enabled: false,
+      host: "localhost".to_string(),
+      port: 1883,
+      username: String::new(),
+      profile: "default".to_string(),
+      accept_commands: false,
+    }
+  }
+}
+
+/// System-wide hotkeys in accelerator syntax (`CommandOrControl+Shift+P`); empty leaves an action unbound.
+#[derive(Clone, Serialize, Deserialize, Type, Debug, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct ShortcutSettings {
+  pub toggle_timer: String,
+  pub skip: String,
+  pub reset: String,
+  /// Shows the floating window, or hides it when it is already visible.
+  pub toggle_floating: String,
+  pub quick_capture: String,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Type, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CloseAction {
+  /// The timer keeps running in the tray; the window comes back with its state intact.
+  HideToTray,
+  // Closing the main window quit the app before this setting existed, so that stays the default.
+  #[default]
+  Quit,
+}
+
+/// What closing and minimizing the main window do, and how quitting is confirmed.
+#[derive(Clone, Serialize, Deserialize, Type, Debug, PartialEq, Eq)]
+#[serde(default)]
+pub struct WindowSettings {
+  pub close_action: CloseAction,
+  pub minimize_to_tray: bool,
+  /// Asks before quitting while a work session is running.
+  pub confirm_quit_during_work: bool,
+}
+
+impl Default for WindowSettings {
+  fn default() -> Self {
+    Self { close_action: CloseAction::Quit, minimize_to_tray: false, confirm_quit_during_work: true }
+  }
+}
+
+/// When the planner's day begins; sessions after midnight but before this hour still count for the previous day.
+#[derive(Clone, Serialize, Deserialize, Type, Debug, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct DaySettings {
+  /// Local hour, 0 to 23; 0 starts the day at midnight.
+  pub start_hour: u32,
+}
+
+/// Launch at login, with the main window hidden so only the tray, timer and reminders start.
+#[derive(Clone, Serialize, Deserialize, Type, Debug, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct AutostartSettings {
+  pub enabled: bool,
+}
+
+/// A folder (for example a synced phone-notes folder) whose `.txt` and `.md` files are imported into the Inbox.
+#[derive(Clone, Serialize, Deserialize, Type, Debug, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct DropFolderSettings {
+  pub enabled: bool,
+  pub path: String,
+}
+
+/// Desktop-shell preferences that live next to `pomodoro_settings.json` but are not part of the timer settings.
+#[derive(Clone, Serialize, Deserialize, Type, Debug, Default)]
+#[serde(default)]
+pub struct DesktopSettings {
+  pub tray: TraySettings,
+  pub floating: FloatingSettings,
+  pub rpc: RpcSettings,
+  pub status_bar: StatusBarSettings,
+  pub hooks: HookSettings,
+  pub webhooks: Vec<Webhook>,
+  pub mqtt: MqttSettings,
+  pub shortcuts: ShortcutSettings,
+  pub drop_folder: DropFolderSettings,
+  pub autostart: AutostartSettings,
+  pub window: WindowSettings,
+  pub day: DaySettings,
+}
+
+pub fn load_desktop_settings(path: &PathBuf) -> DesktopSettings {
+  if let Ok(content) = fs::read_to_string(path) {
+    if let Ok(mut settings) = serde_json::from_str::<DesktopSettings>(&content) {
+      // `update_desktop_settings` rejects other hours; a hand-edited file falls back to midnight.
+      if settings.day.start_hour > 23 {
+        settings.day = DaySettings::default();
+      }
+      return settings;
+    }
+  }
+  DesktopSettings::default()
+}
+
+pub fn save_desktop_settings(path: &PathBuf, settings: &DesktopSettings) -> AppResult<()> {
+  fs::write(path, serde_json::to_string_pretty(settings)?)?;
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{load_desktop_settings, CloseAction, DesktopSettings, TrayClickAction};
+
+  #[test]
+  fn missing_sections_fall_back_to_defaults() {
+    let settings = serde_json::from_str::<DesktopSettings>("{}").expect("should parse");
+    assert_eq!(settings.tray.single_click, TrayClickAction::None);
+    assert_eq!(settings.tray.double_click, TrayClickAction::OpenMain);
+    assert!(settings.tray.show_countdown);
+    assert!(!settings.rpc.enabled);
+    assert!(!settings.status_bar.enabled);
+    assert_eq!(settings.window.close_action, CloseAction::Quit);
+    assert!(settings.window.confirm_quit_during_work);
+  }
+
+  #[test]
+  fn partial_tray_settings_keep_other_defaults() {
+    let settings = serde_json::from_str::<DesktopSettings>(r#"{"tray":{"middle_click":"toggleTimer"}}"#).expect("should parse");
+    assert_eq!(settings.tray.middle_click, TrayClickAction::ToggleTimer);
+    assert_eq!(settings.tray.double_click, TrayClickAction::OpenMain);
+  }
+
+  #[test]
+  fn out_of_range_day_start_falls_back_to_midnight() {
+    let path = std::env::temp_dir().join(format!("daily-planner-desktop-settings-{}.json", std::process::id()));
+    std::fs::write(&path, r#"{"day":{"start_hour":30},"rpc":{"enabled":true}}"#).unwrap();
+    let settings = load_desktop_settings(&path);
+    assert_eq!(settings.day.start_hour, 0);
+    assert!(settings.rpc.enabled);
+    let _ = std::fs::remove_file(path);
+  }
+}
@@ -0,0 +1,327 @@
+use crate::actions::{MainRequest, MainRequestQueue};
+use crate::desktop_settings::DropFolderSettings;
+use crate::error::{AppError, AppResult};
+use crate::events::{emit_main_requests_queued, report_error};
+use crate::AppState;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Manager};
+
+/// Sync clients write into these folders in ways file-change notifications miss (network drives, rename tricks), so the folder is polled.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Files changed more recently than this may still be arriving from the sync client.
+const SETTLE_TIME: Duration = Duration::from_secs(3);
+const IMPORTED_DIR: &str = "imported";
+/// Oldest entries are dropped past this, which only matters if the same note comes back after years.
+const LOG_LIMIT: usize = 2000;
+
+/// Handle of the running watcher, so changing the folder restarts it.
+#[derive(Default)]
+pub struct DropFolderWatcher(Mutex<Option<JoinHandle<()>>>);
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+struct LogEntry {
+  file: String,
+  fingerprint: String,
+  imported_at: String,
+  /// Set when the content had been imported before, so the file was only moved away.
+  duplicate: bool,
+}
+
+/// Every file taken from the folder, persisted so a sync client restoring an already imported note does not create it again.
+struct ImportLog {
+  path: PathBuf,
+  entries: Vec<LogEntry>,
+}
+
+impl ImportLog {
+  fn load(path: PathBuf) -> Self {
+    let entries = fs::read_to_string(&path)
+      .ok()
+      .and_then(|content| serde_json::from_str::<Vec<LogEntry>>(&content).ok())
+      .unwrap_or_default();
+    Self { path, entries }
+  }
+
+  fn save(&self) -> AppResult<()> {
+    fs::write(&self.path, serde_json::to_string_pretty(&self.entries)?)?;
+    Ok(())
+  }
+
+  fn contains(&self, fingerprint: &str) -> bool {
+    self.entries.iter().any(|entry| entry.fingerprint == fingerprint)
+  }
+
+  fn record(&mut self, entry: LogEntry) {
+    self.entries.push(entry);
+    if self.entries.len() > LOG_LIMIT {
+      let excess = self.entries.len() - LOG_LIMIT;
+      self.entries.drain(..excess);
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Note {
+  pub title: String,
+  pub notes: Option<String>,
+}
+
+/// FNV-1a over the text with line endings and trailing whitespace normalised, so a copy re-saved on another platform still matches.
+fn fingerprint(content: &str) -> String {
+  let normalized = content.trim_start_matches('\u{feff}').lines().map(str::trim_end).collect::<Vec<_>>().join("\n");
+  let hash = normalized.trim().bytes().fold(0xcbf29ce484222325u64, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3));
+  format!("{:016x}", hash)
+}
+
+/// The first non-empty line becomes the title (without Markdown heading marks); the rest becomes the notes.
+fn parse_note(content: &str, markdown: bool) -> Option<Note> {
+  let mut lines = content.trim_start_matches('\u{feff}').lines().skip_while(|line| line.trim().is_empty());
+  let first = lines.next()?.trim();
+  let title = if markdown { first.trim_start_matches('#').trim() } else { first };
+  let notes = lines.collect::<Vec<_>>().join("\n").trim().to_string();
+  if title.is_empty() {
+    return None;
+  }
+  Some(Note { title: title.to_string(), notes: (!notes.is_empty()).then_some(notes) })
+}
+
+fn note_kind(path: &Path) -> Option<bool> {
+  let name = path.file_name()?.to_str()?;
+  if name.starts_with('.') {
+    return None;
+  }
+  match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+    "txt" => Some(false),
+    "md" | "markdown" => Some(true),
+    _ => None,
+  }
+}
+
+/// `imported/<name>`, numbered when a file of that name was imported before.
+fn imported_destination(folder: &Path, path: &Path) -> PathBuf {
+  let dir = folder.join(IMPORTED_DIR);
+  let name = path.file_name().unwrap_or_default();
+  let candidate = dir.join(name);
+  if !candidate.exists() {
+    return candidate;
+  }
+  let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+  let extension = path.extension().unwrap_or_default().to_string_lossy();
+  (2..).map(|index| dir.join(format!("{}-{}.{}", stem, index, extension))).find(|candidate| !candidate.exists()).unwrap()
+}
+
+/// Hands the note to `persist` before logging its content and moving the file out of the way, so a failed persist leaves
+/// the file in place for the next pass. Empty files and content that was imported before are only moved.
+fn import_file(
+  folder: &Path,
+  path: &Path,
+  markdown: bool,
+  log: &mut ImportLog,
+  persist: &mut dyn FnMut(Note) -> AppResult<()>,
+) -> AppResult<()> {
+  let content = String::from_utf8_lossy(&fs::read(path)?).to_string();
+  let fingerprint = fingerprint(&content);
+  let duplicate = log.contains(&fingerprint);
+  let note = if duplicate { None } else { parse_note(&content, markdown) };
+
+  let imported = note.is_some();
+  if let Some(note) = note {
+    persist(note)?;
+  }
+  // Logged before the move: a file that cannot be moved is recognised as a duplicate on the next pass.
+  if imported || duplicate {
+    log.record(LogEntry {
+      file: path.file_name().unwrap_or_default().to_string_lossy().to_string(),
+      fingerprint,
+      imported_at: chrono::Local::now().to_rfc3339(),
+      duplicate,
+    });
+  }
+  fs::create_dir_all(folder.join(IMPORTED_DIR))?;
+  fs::rename(path, imported_destination(folder, path))?;
+  Ok(())
+}
+
+fn is_settled(path: &Path, now: SystemTime) -> bool {
+  fs::metadata(path)
+    .and_then(|metadata| metadata.modified())
+    .map(|modified| now.duration_since(modified).is_ok_and(|age| age >= SETTLE_TIME))
+    .unwrap_or(false)
+}
+
+/// Imports every settled note file in name order and returns how many notes were persisted; a file that fails is left in
+/// place and retried on the next pass.
+fn scan(folder: &Path, log: &mut ImportLog, now: SystemTime, persist: &mut dyn FnMut(Note) -> AppResult<()>) -> (usize, AppResult<()>) {
+  let mut files = match fs::read_dir(folder) {
+    Ok(entries) => entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()).filter(|path| path.is_file()).collect::<Vec<_>>(),
+    Err(err) => return (0, Err(AppError::Io(format!("{}: {}", folder.display(), err)))),
+  };
+  files.sort();
+
+  let mut imported = 0;
+  let mut failures = Vec::new();
+  let mut changed = false;
+  let mut persist_counted = |note: Note| -> AppResult<()> {
+    persist(note)?;
+    imported += 1;
+    Ok(())
+  };
+  for path in files {
+    let Some(markdown) = note_kind(&path) else { continue };
+    if !is_settled(&path, now) {
+      continue;
+    }
+    // Saved even when the file failed: it may have been logged before its move failed.
+    changed = true;
+    if let Err(err) = import_file(folder, &path, markdown, log, &mut persist_counted) {
+      failures.push(format!("{}: {}", path.display(), err));
+    }
+  }
+
+  let saved = if changed { log.save() } else { Ok(()) };
+  let result = if failures.is_empty() { saved } else { saved.and(Err(AppError::Io(failures.join("; ")))) };
+  (imported, result)
+}
+
+async fn run(handle: AppHandle, folder: PathBuf, mut log: ImportLog) {
+  // Only the first error of a streak is reported, so a missing folder does not raise one every few seconds.
+  let mut failing = false;
+  loop {
+    let queue = handle.state::<MainRequestQueue>().inner();
+    let (imported, result) = scan(&folder, &mut log, SystemTime::now(), &mut |note| {
+      queue.push(MainRequest::ImportNote { title: note.title, notes: note.notes })
+    });
+    if imported > 0 {
+      report_error(&handle, "drop folder", emit_main_requests_queued(&handle));
+    }
+    match result {
+      Ok(()) => failing = false,
+      Err(err) if !failing => {
+        failing = true;
+        report_error(&handle, "drop folder", Err(err));
+      }
+      Err(_) => {}
+    }
+    tokio::time::sleep(POLL_INTERVAL).await;
+  }
+}
+
+fn should_watch(settings: &DropFolderSettings) -> bool {
+  settings.enabled && !settings.path.trim().is_empty()
+}
+
+/// Starts, restarts or stops the watcher to match the `drop_folder` desktop setting.
+pub fn sync(handle: &AppHandle) {
+  let settings = handle.state::<AppState>().desktop_settings.lock().unwrap().drop_folder.clone();
+  let watcher = handle.state::<DropFolderWatcher>();
+  let mut running = watcher.0.lock().unwrap();
+
+  if let Some(task) = running.take() {
+    task.abort();
+  }
+  if !should_watch(&settings) {
+    return;
+  }
+
+  let log = ImportLog::load(crate::get_config_path(handle).join("drop_folder_log.json"));
+  *running = Some(tauri::async_runtime::spawn(run(handle.clone(), PathBuf::from(settings.path.trim()), log)));
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{fingerprint, parse_note, scan, ImportLog, Note, IMPORTED_DIR};
+  use crate::actions::{MainRequest, MainRequestQueue};
+  use crate::error::AppResult;
+  use std::fs;
+  use std::path::Path;
+  use std::time::{Duration, SystemTime};
+
+  #[test]
+  fn first_line_is_the_title_and_the_rest_the_notes() {
+    assert_eq!(
+      parse_note("\u{feff}\n# Call the dentist\n\nAsk about Friday\n", true),
+      Some(Note { title: "Call the dentist".to_string(), notes: Some("Ask about Friday".to_string()) })
+    );
+    assert_eq!(parse_note("#hashtag idea", false), Some(Note { title: "#hashtag idea".to_string(), notes: None }));
+    assert_eq!(parse_note(" \n\n", false), None);
+  }
+
+  #[test]
+  fn fingerprint_ignores_line_endings_and_trailing_space() {
+    assert_eq!(fingerprint("Buy milk\r\noat  \r\n"), fingerprint("Buy milk\noat\n"));
+    assert_ne!(fingerprint("Buy milk"), fingerprint("Buy oat milk"));
+  }
+
+  /// Scans without a queue, returning the notes that would have been queued.
+  fn scan_notes(folder: &Path, log: &mut ImportLog, now: SystemTime) -> (Vec<Note>, AppResult<()>) {
+    let mut notes = Vec::new();
+    let (imported, result) = scan(folder, log, now, &mut |note| {
+      notes.push(note);
+      Ok(())
+    });
+    assert_eq!(imported, notes.len());
+    (notes, result)
+  }
+
+  #[test]
+  fn resynced_files_are_moved_without_importing_them_again() {
+    let folder = std::env::temp_dir().join(format!("daily-planner-drop-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&folder);
+    fs::create_dir_all(&folder).unwrap();
+    let mut log = ImportLog { path: folder.join("log.json"), entries: Vec::new() };
+    let later = SystemTime::now() + Duration::from_secs(60);
+
+    fs::write(folder.join("milk.txt"), "Buy milk\noat").unwrap();
+    fs::write(folder.join("photo.jpg"), "not a note").unwrap();
+    let (notes, result) = scan_notes(&folder, &mut log, later);
+    result.expect("scan");
+    assert_eq!(notes, vec![Note { title: "Buy milk".to_string(), notes: Some("oat".to_string()) }]);
+    assert!(folder.join(IMPORTED_DIR).join("milk.txt").exists());
+    assert!(folder.join("photo.jpg").exists());
+
+    // Files that are still being written wait for the next pass.
+    fs::write(folder.join("milk.txt"), "Buy milk\r\noat\r\n").unwrap();
+    let (notes, _) = scan_notes(&folder, &mut log, SystemTime::now());
+    assert!(notes.is_empty());
+    assert!(folder.join("milk.txt").exists());
+
+    let (notes, result) = scan_notes(&folder, &mut ImportLog::load(folder.join("log.json")), later);
+    result.expect("scan");
+    assert!(notes.is_empty());
+    assert!(folder.join(IMPORTED_DIR).join("milk-2.txt").exists());
+    let _ = fs::remove_dir_all(&folder);
+  }
+
+  #[test]
+  fn files_stay_in_place_until_their_note_is_persisted() {
+    let folder = std::env::temp_dir().join(format!("daily-planner-drop-persist-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&folder);
+    fs::create_dir_all(&folder).unwrap();
+    let mut log = ImportLog { path: folder.join("log.json"), entries: Vec::new() };
+    let later = SystemTime::now() + Duration::from_secs(60);
+    // The queue's directory does not exist, so persisting the request fails.
+    let queue = MainRequestQueue::load(folder.join("missing").join("main_requests.json"));
+    let mut persist = |note: Note| queue.push(MainRequest::ImportNote { title: note.title, notes: note.notes });
+
+    fs::write(folder.join("milk.txt"), "Buy milk").unwrap();
+    let (imported, result) = scan(&folder, &mut log, later, &mut persist);
+    assert!(result.is_err());
+    assert_eq!(imported, 0);
+    assert!(folder.join("milk.txt").exists());
+    assert!(!folder.join(IMPORTED_DIR).join("milk.txt").exists());
+    assert!(!log.contains(&fingerprint("Buy milk")));
+
+    fs::create_dir_all(folder.join("missing")).unwrap();
+    let (imported, result) = scan(&folder, &mut log, later, &mut persist);
+    result.expect("scan");
+    assert_eq!(imported, 1);
+    assert!(folder.join(IMPORTED_DIR).join("milk.txt").exists());
+    assert!(fs::read_to_string(folder.join("missing").join("main_requests.json")).unwrap().contains("Buy milk"));
+    let _ = fs::remove_dir_all(&folder);
+  }
+}
@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// Error returned by every command and carried by `backend_error`; serialized as `{ kind, message }` for the frontend.
+#[derive(Clone, Serialize, Deserialize, Type, Debug, thiserror::Error)]
+#[serde(tag = "kind", content = "message", rename_all = "camelCase")]
+pub enum AppError {
+  #[error("window error: {0}")]
+  Window(String),
+  #[error("file error: {0}")]
+  Io(String),
+  #[error("invalid data: {0}")]
+  Serialization(String),
+  #[error("notification error: {0}")]
+  Notification(String),
+  #[error("event error: {0}")]
+  Emit(String),
+  /// A global shortcut that is invalid, assigned twice or already taken by another application.
+  #[error("shortcut error: {0}")]
+  Shortcut(String),
+}
+
+pub type AppResult<T> = Result<T, AppError>;
+
+impl From<std::io::Error> for AppError {
+  fn from(err: std::io::Error) -> Self {
+    Self::Io(err.to_string())
+  }
+}
+
+impl From<serde_json::Error> for AppError {
+  fn from(err: serde_json::Error) -> Self {
+    Self::Serialization(err.to_string())
+  }
+}
+
+impl From<tauri::Error> for AppError {
+  fn from(err: tauri::Error) -> Self {
+    Self::Window(err.to_string())
+  }
+}
+
+impl From<tauri_plugin_global_shortcut::Error> for AppError {
+  fn from(err: tauri_plugin_global_shortcut::Error) -> Self {
+    Self::Shortcut(err.to_string())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::AppError;
+  use serde_json::json;
+
+  #[test]
+  fn errors_serialize_with_kind_and_message() {
+    let err = AppError::from(std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied"));
+    assert_eq!(serde_json::to_value(&err).unwrap(), json!({ "kind": "io", "message": "denied" }));
+  }
+}
@@ -0,0 +1,136 @@
+use crate::desktop_settings::DesktopSettings;
+use crate::error::{AppError, AppResult};
+use crate::floating::FloatingMode;
+use crate::rollover::RolloverSummary;
+use crate::{PomodoroSettings, PomodoroState};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, EventTarget, Manager};
+use tauri_specta::Event;
+use tokio::sync::broadcast;
+
+/// Emitted to every window: the timer is rendered in the main view, the floating window and the tray.
+#[derive(Clone, Serialize, Deserialize, Type, Event, Debug)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum TimerEvent {
+  Tick(PomodoroState),
+  WorkCompleted { work_duration: u32 },
+  BreakCompleted,
+  /// The `stop_after_sessions` goal was met and the timer stopped.
+  GoalReached { sessions_completed: u32 },
+}
+
+#[derive(Clone, Serialize, Deserialize, Type, Event, Debug)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum SettingsEvent {
+  PomodoroChanged(PomodoroSettings),
+  DesktopChanged(DesktopSettings),
+}
+
+/// Emitted to the `floating` window only, except `StatusBarHint` which goes to `main`.
+#[derive(Clone, Serialize, Deserialize, Type, Event, Debug)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum FloatingEvent {
+  PreferencesChanged { theme: String, opacity: f64 },
+  ModeChanged { mode: FloatingMode },
+  ClickThroughChanged { enabled: bool },
+  /// macOS has no floating window; the main view points at the menu bar item instead.
+  StatusBarHint,
+}
+
+/// A failure outside any command call (timer loop, tray menu, notifications); the timer keeps running.
+#[derive(Clone, Serialize, Deserialize, Type, Event, Debug)]
+#[tauri_specta(event_name = "backend_error")]
+pub struct BackendError {
+  pub context: String,
+  pub error: AppError,
+}
+
+/// The planner day changed. The timer announces it first, without `tasks`; the main window then rolls its tasks over with
+/// `roll_over_tasks`, which announces the day again with what it did for the Review workspace.
+#[derive(Clone, Serialize, Deserialize, Type, Event, Debug)]
+#[tauri_specta(event_name = "day_rolled_over")]
+pub struct DayRolledOver {
+  /// `None` when the tasks are rolled over for the first time.
+  pub from: Option<String>,
+  pub to: String,
+  pub tasks: Option<RolloverSummary>,
+}
+
+/// Tells the main window to drain its request queue with `take_main_requests`.
+#[derive(Clone, Serialize, Deserialize, Type, Event, Debug)]
+pub struct MainRequestsQueued;
+
+fn emit_error(err: tauri::Error) -> AppError {
+  AppError::Emit(err.to_string())
+}
+
+/// In-process fan-out of timer events for integrations outside the webviews (RPC subscribers and the like).
+pub struct TimerBus(broadcast::Sender<TimerEvent>);
+
+impl Default for TimerBus {
+  fn default() -> Self {
+    Self(broadcast::channel(64).0)
+  }
+}
+
+impl TimerBus {
+  pub fn subscribe(&self) -> broadcast::Receiver<TimerEvent> {
+    self.0.subscribe()
+  }
+}
+
+pub fn emit_timer(handle: &AppHandle, event: TimerEvent) -> AppResult<()> {
+  if let Some(bus) = handle.try_state::<TimerBus>() {
+    // Sending only fails when nobody is subscribed.
+    let _ = bus.0.send(event.clone());
+  }
+  event.emit(handle).map_err(emit_error)
+}
+
+pub fn emit_settings(handle: &AppHandle, event: SettingsEvent) -> AppResult<()> {
+  event
+    .emit_filter(handle, |target| {
+      matches!(target, EventTarget::WebviewWindow { label } if label == "main" || label == "floating-settings")
+    })
+    .map_err(emit_error)
+}
+
+pub fn emit_floating(handle: &AppHandle, event: FloatingEvent) -> AppResult<()> {
+  let label = if matches!(event, FloatingEvent::StatusBarHint) { "main" } else { "floating" };
+  event.emit_to(handle, EventTarget::webview_window(label)).map_err(emit_error)
+}
+
+pub fn emit_main_requests_queued(handle: &AppHandle) -> AppResult<()> {
+  MainRequestsQueued.emit_to(handle, EventTarget::webview_window("main")).map_err(emit_error)
+}
+
+pub fn emit_day_rolled_over(handle: &AppHandle, from: Option<String>, to: String, tasks: Option<RolloverSummary>) -> AppResult<()> {
+  DayRolledOver { from, to, tasks }.emit(handle).map_err(emit_error)
+}
+
+/// Surfaces `result`'s error as a `backend_error` event; used where there is no command caller to return it to.
+pub fn report_error(handle: &AppHandle, context: &str, result: AppResult<()>) {
+  let Err(error) = result else { return; };
+  let event = BackendError { context: context.to_string(), error };
+  if let Err(err) = event.emit(handle) {
+    eprintln!("failed to report backend error {:?}: {}", event, err);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{FloatingEvent, TimerEvent};
+  use crate::floating::FloatingMode;
+use crate::rollover::RolloverSummary;
+  use serde_json::json;
+
+  #[test]
+  fn payloads_are_tagged_by_kind() {
+    let mode = serde_json::to_value(FloatingEvent::ModeChanged { mode: FloatingMode::Bar }).unwrap();
+    assert_eq!(mode, json!({ "kind": "modeChanged", "mode": "bar" }));
+
+    let done = serde_json::to_value(TimerEvent::WorkCompleted { work_duration: 25 }).unwrap();
+    assert_eq!(done, json!({ "kind": "workCompleted", "work_duration": 25 }));
+  }
+}
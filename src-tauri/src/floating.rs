@@ -0,0 +1,594 @@
+use crate::desktop_settings::{BarEdge, FloatingSettings};
+use crate::error::AppResult;
+use crate::events::{emit_floating, report_error, FloatingEvent};
+use crate::AppState;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use tauri::{menu::CheckMenuItem, AppHandle, Manager, Monitor, PhysicalPosition, PhysicalSize, WebviewWindow, WindowEvent, Wry};
+use tokio::time::{sleep, Duration};
+
+/// Moves and resizes arrive as a burst while the user drags; geometry is only persisted once they stop.
+const GEOMETRY_SETTLE_DELAY: Duration = Duration::from_millis(400);
+
+#[derive(Clone, Copy, Serialize, Deserialize, Type, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum FloatingMode {
+  Standard,
+  Mini,
+  Bar,
+}
+
+impl FloatingMode {
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Self::Standard => "standard",
+      Self::Mini => "mini",
+      Self::Bar => "bar",
+    }
+  }
+}
+
+pub fn floating_window_spec(mode: &str) -> (&'static str, f64, f64, bool) {
+  match mode {
+    "mini" => ("/?view=floating&mode=mini", 232.0, 56.0, true),
+    // The bar is sized from monitor geometry by `dock_bar`; these are only the pre-docking defaults.
+    "bar" => ("/?view=floating&mode=bar", 800.0, 6.0, false),
+    _ => ("/?view=floating", 312.0, 208.0, true),
+  }
+}
+
+/// For `bar`, width is the bar length and height its thickness.
+pub fn preferred_floating_size(mode: &str, width: Option<f64>, height: Option<f64>) -> (f64, f64) {
+  let (_, default_width, default_height, _) = floating_window_spec(mode);
+  let (min_width, min_height, max_width, max_height) = match mode {
+    "mini" => (208.0, 56.0, 320.0, 120.0),
+    "bar" => (240.0, 4.0, 16384.0, 32.0),
+    _ => (292.0, 188.0, 560.0, 360.0),
+  };
+
+  (
+    width.unwrap_or(default_width).clamp(min_width, max_width),
+    height.unwrap_or(default_height).clamp(min_height, max_height),
+  )
+}
+
+/// Outer position in physical pixels, inner size in logical pixels.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+pub struct FloatingGeometry {
+  pub x: i32,
+  pub y: i32,
+  pub width: f64,
+  pub height: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScreenRect {
+  pub x: i32,
+  pub y: i32,
+  pub width: u32,
+  pub height: u32,
+}
+
+impl ScreenRect {
+  fn overlap(&self, x: i32, y: i32, width: u32, height: u32) -> i64 {
+    let left = self.x.max(x) as i64;
+    let top = self.y.max(y) as i64;
+    let right = (self.x as i64 + self.width as i64).min(x as i64 + width as i64);
+    let bottom = (self.y as i64 + self.height as i64).min(y as i64 + height as i64);
+    (right - left).max(0) * (bottom - top).max(0)
+  }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct FloatingGeometryStore {
+  /// Keyed by `mode@monitor-layout`.
+  pub layouts: HashMap<String, FloatingGeometry>,
+  /// Latest geometry per mode, used for monitor layouts that have not been seen yet.
+  pub last: HashMap<String, FloatingGeometry>,
+}
+
+impl FloatingGeometryStore {
+  pub fn lookup(&self, mode: &str, layout: &str) -> Option<FloatingGeometry> {
+    self.layouts.get(&format!("{}@{}", mode, layout)).or_else(|| self.last.get(mode)).copied()
+  }
+
+  pub fn remember(&mut self, mode: &str, layout: &str, geometry: FloatingGeometry) {
+    self.layouts.insert(format!("{}@{}", mode, layout), geometry);
+    self.last.insert(mode.to_string(), geometry);
+  }
+}
+
+pub struct FloatingTracker {
+  pub mode: String,
+  generation: u64,
+  store: FloatingGeometryStore,
+  path: PathBuf,
+  click_through: bool,
+  pub click_through_item: Option<CheckMenuItem<Wry>>,
+  in_break: bool,
+  main_focused: bool,
+  /// Set when an auto-hide rule hid the window, so only those windows are shown again.
+  auto_hidden: bool,
+}
+
+impl FloatingTracker {
+  pub fn load(path: PathBuf) -> Self {
+    let store = fs::read_to_string(&path)
+      .ok()
+      .and_then(|content| serde_json::from_str::<FloatingGeometryStore>(&content).ok())
+      .unwrap_or_default();
+    Self {
+      mode: "standard".to_string(),
+      generation: 0,
+      store,
+      path,
+      click_through: false,
+      click_through_item: None,
+      in_break: false,
+      main_focused: false,
+      auto_hidden: false,
+    }
+  }
+
+  fn save(&self) -> AppResult<()> {
+    fs::write(&self.path, serde_json::to_string_pretty(&self.store)?)?;
+    Ok(())
+  }
+}
+
+pub fn monitor_layout_key(screens: &[ScreenRect]) -> String {
+  let mut parts = screens
+    .iter()
+    .map(|screen| format!("{}x{}+{}+{}", screen.width, screen.height, screen.x, screen.y))
+    .collect::<Vec<_>>();
+  parts.sort();
+  parts.join(",")
+}
+
+/// Picks the screen showing most of the window, or the first screen if the window is entirely off-screen.
+pub fn screen_for_window(x: i32, y: i32, width: u32, height: u32, screens: &[ScreenRect]) -> Option<ScreenRect> {
+  screens
+    .iter()
+    .copied()
+    .filter(|screen| screen.overlap(x, y, width, height) > 0)
+    .max_by_key(|screen| screen.overlap(x, y, width, height))
+    .or_else(|| screens.first().copied())
+}
+
+pub fn clamp_to_screen(x: i32, y: i32, width: u32, height: u32, screen: ScreenRect) -> (i32, i32) {
+  let max_x = screen.x + screen.width as i32 - width as i32;
+  let max_y = screen.y + screen.height as i32 - height as i32;
+  (x.clamp(screen.x, max_x.max(screen.x)), y.clamp(screen.y, max_y.max(screen.y)))
+}
+
+/// Physical `(x, y, width, height)` of a bar docked to `edge` of a monitor work area.
+pub fn bar_geometry(work_area: ScreenRect, scale: f64, edge: BarEdge, thickness: u32) -> (i32, i32, u32, u32) {
+  let (_, thickness) = preferred_floating_size("bar", None, Some(thickness as f64));
+  let thickness = ((thickness * scale).round() as u32).max(1);
+  match edge {
+    BarEdge::Top => (work_area.x, work_area.y, work_area.width, thickness),
+    BarEdge::Bottom => (work_area.x, work_area.y + work_area.height as i32 - thickness as i32, work_area.width, thickness),
+    BarEdge::Left => (work_area.x, work_area.y, thickness, work_area.height),
+    BarEdge::Right => (work_area.x + work_area.width as i32 - thickness as i32, work_area.y, thickness, work_area.height),
+  }
+}
+
+pub fn snap_to_edges(x: i32, y: i32, width: u32, height: u32, screen: ScreenRect, distance: i32) -> (i32, i32) {
+  let right = screen.x + screen.width as i32 - width as i32;
+  let bottom = screen.y + screen.height as i32 - height as i32;
+  let snapped_x = if (x - screen.x).abs() <= distance {
+    screen.x
+  } else if (x - right).abs() <= distance {
+    right
+  } else {
+    x
+  };
+  let snapped_y = if (y - screen.y).abs() <= distance {
+    screen.y
+  } else if (y - bottom).abs() <= distance {
+    bottom
+  } else {
+    y
+  };
+  (snapped_x, snapped_y)
+}
+
+/// Mirrors `normalizeOpacity` in the floating views so a fully transparent window can't be configured.
+pub fn normalize_opacity(opacity: f64) -> f64 {
+  if opacity.is_finite() { opacity.clamp(0.45, 1.0) } else { 0.96 }
+}
+
+pub fn should_auto_hide(settings: &FloatingSettings, in_break: bool, main_focused: bool) -> bool {
+  (settings.hide_during_breaks && in_break) || (settings.hide_when_main_focused && main_focused)
+}
+
+fn monitor_bounds(monitor: &Monitor) -> ScreenRect {
+  ScreenRect { x: monitor.position().x, y: monitor.position().y, width: monitor.size().width, height: monitor.size().height }
+}
+
+pub fn monitor_work_area(monitor: &Monitor) -> ScreenRect {
+  let area = monitor.work_area();
+  ScreenRect { x: area.position.x, y: area.position.y, width: area.size.width, height: area.size.height }
+}
+
+/// Work areas with the primary monitor first, plus the layout key of the current monitor setup.
+fn current_screens(handle: &AppHandle) -> (Vec<ScreenRect>, String) {
+  let mut monitors = handle.available_monitors().unwrap_or_default();
+  if let Ok(Some(primary)) = handle.primary_monitor() {
+    if let Some(index) = monitors.iter().position(|monitor| monitor_bounds(monitor) == monitor_bounds(&primary)) {
+      let primary = monitors.remove(index);
+      monitors.insert(0, primary);
+    }
+  }
+  let bounds = monitors.iter().map(monitor_bounds).collect::<Vec<_>>();
+  (monitors.iter().map(monitor_work_area).collect(), monitor_layout_key(&bounds))
+}
+
+fn restored_geometry(handle: &AppHandle, mode: &str) -> Option<FloatingGeometry> {
+  let (_, layout) = current_screens(handle);
+  let state = handle.state::<AppState>();
+  let mut tracker = state.floating.lock().unwrap();
+  tracker.mode = mode.to_string();
+  if mode == "bar" {
+    return None;
+  }
+  tracker.store.lookup(mode, &layout)
+}
+
+/// Docks a bar-mode window to the configured edge of its monitor, or of the monitor under the cursor for new windows.
+fn dock_bar(window: &WebviewWindow, newly_created: bool) {
+  let handle = window.app_handle();
+  let cursor_monitor = || {
+    handle
+      .cursor_position()
+      .ok()
+      .and_then(|cursor| handle.monitor_from_point(cursor.x, cursor.y).ok().flatten())
+  };
+  let monitor = if newly_created {
+    cursor_monitor().or_else(|| window.current_monitor().ok().flatten())
+  } else {
+    window.current_monitor().ok().flatten().or_else(cursor_monitor)
+  };
+  let Some(monitor) = monitor.or_else(|| handle.primary_monitor().ok().flatten()) else { return; };
+
+  let settings = handle.state::<AppState>().desktop_settings.lock().unwrap().floating.clone();
+  let (x, y, width, height) = bar_geometry(monitor_work_area(&monitor), monitor.scale_factor(), settings.bar_edge, settings.bar_thickness);
+  let _ = window.set_size(PhysicalSize::new(width, height));
+  let _ = window.set_position(PhysicalPosition::new(x, y));
+}
+
+/// Re-docks the bar after its edge or thickness changed.
+pub fn redock_bar(handle: &AppHandle) {
+  if handle.state::<AppState>().floating.lock().unwrap().mode != "bar" {
+    return;
+  }
+  if let Some(window) = handle.get_webview_window("floating") {
+    dock_bar(&window, false);
+  }
+}
+
+fn place_window(window: &WebviewWindow, geometry: FloatingGeometry) {
+  let (screens, _) = current_screens(window.app_handle());
+  let Ok(outer) = window.outer_size() else { return; };
+  if let Some(screen) = screen_for_window(geometry.x, geometry.y, outer.width, outer.height, &screens) {
+    let (x, y) = clamp_to_screen(geometry.x, geometry.y, outer.width, outer.height, screen);
+    let _ = window.set_position(PhysicalPosition::new(x, y));
+  }
+}
+
+pub fn build_floating_window(handle: &AppHandle, mode: &str, width: Option<f64>, height: Option<f64>) -> AppResult<()> {
+  let (url, _, _, resizable) = floating_window_spec(mode);
+  let restored = restored_geometry(handle, mode);
+  let (width, height) = match restored {
+    Some(geometry) => preferred_floating_size(mode, Some(geometry.width), Some(geometry.height)),
+    None => preferred_floating_size(mode, width, height),
+  };
+  let window = tauri::WebviewWindowBuilder::new(
+    handle,
+    "floating",
+    tauri::WebviewUrl::App(url.into())
+  )
+  .title("Floating")
+  .inner_size(width, height)
+  .resizable(resizable)
+  .decorations(false)
+  .transparent(false)
+  .always_on_top(true)
+  .skip_taskbar(true)
+  .visible(false)
+  .build()?;
+
+  if mode == "bar" {
+    dock_bar(&window, true);
+  } else if let Some(geometry) = restored {
+    place_window(&window, geometry);
+  }
+  let state = handle.state::<AppState>();
+  let settings = state.desktop_settings.lock().unwrap().floating.clone();
+  apply_window_opacity(&window, settings.opacity);
+  let (click_through, hidden) = {
+    let mut tracker = state.floating.lock().unwrap();
+    tracker.auto_hidden = should_auto_hide(&settings, tracker.in_break, tracker.main_focused);
+    (tracker.click_through, tracker.auto_hidden)
+  };
+  window.set_ignore_cursor_events(click_through)?;
+  if !hidden {
+    window.show()?;
+  }
+  track_floating_window(&window);
+  Ok(())
+}
+
+/// Resizes an existing floating window for `mode`, preferring the geometry remembered for that mode.
+pub fn resize_floating_window(window: &WebviewWindow, mode: &str, width: Option<f64>, height: Option<f64>) -> AppResult<()> {
+  let (_, _, _, resizable) = floating_window_spec(mode);
+  let restored = restored_geometry(window.app_handle(), mode);
+  let (width, height) = match restored {
+    Some(geometry) => preferred_floating_size(mode, Some(geometry.width), Some(geometry.height)),
+    None => preferred_floating_size(mode, width, height),
+  };
+  window.set_resizable(resizable)?;
+  if mode == "bar" {
+    dock_bar(window, false);
+    return Ok(());
+  }
+  window.set_size(tauri::Size::Logical(tauri::LogicalSize { width, height }))?;
+  if let Some(geometry) = restored {
+    place_window(window, geometry);
+  }
+  Ok(())
+}
+
+pub fn apply_window_opacity(window: &WebviewWindow, opacity: f64) {
+  let opacity = normalize_opacity(opacity);
+  let target = window.clone();
+  let _ = window.run_on_main_thread(move || {
+    #[cfg(target_os = "linux")]
+    {
+      use gtk::prelude::WidgetExt;
+      if let Ok(gtk_window) = target.gtk_window() {
+        gtk_window.set_opacity(opacity);
+      }
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+      use windows::Win32::Foundation::COLORREF;
+      use windows::Win32::UI::WindowsAndMessaging::{GetWindowLongPtrW, SetLayeredWindowAttributes, SetWindowLongPtrW, GWL_EXSTYLE, LWA_ALPHA, WS_EX_LAYERED};
+      if let Ok(hwnd) = target.hwnd() {
+        unsafe {
+          let style = GetWindowLongPtrW(hwnd, GWL_EXSTYLE);
+          SetWindowLongPtrW(hwnd, GWL_EXSTYLE, style | WS_EX_LAYERED.0 as isize);
+          let _ = SetLayeredWindowAttributes(hwnd, COLORREF(0), (opacity * 255.0).round() as u8, LWA_ALPHA);
+        }
+      }
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+    let _ = (target, opacity);
+  });
+}
+
+/// Lets clicks pass through the floating window; the tray item is the way back out.
+pub fn set_click_through(handle: &AppHandle, enabled: bool) -> AppResult<()> {
+  if let Some(window) = handle.get_webview_window("floating") {
+    window.set_ignore_cursor_events(enabled)?;
+  }
+  let item = {
+    let state = handle.state::<AppState>();
+    let mut tracker = state.floating.lock().unwrap();
+    tracker.click_through = enabled;
+    tracker.click_through_item.clone()
+  };
+  if let Some(item) = item {
+    item.set_checked(enabled)?;
+  }
+  emit_floating(handle, FloatingEvent::ClickThroughChanged { enabled })
+}
+
+pub fn is_click_through(handle: &AppHandle) -> bool {
+  handle.state::<AppState>().floating.lock().unwrap().click_through
+}
+
+/// Shows or hides the floating window according to the auto-hide rules.
+pub fn sync_auto_hide(handle: &AppHandle) {
+  let Some(window) = handle.get_webview_window("floating") else { return; };
+  let visible = window.is_visible().unwrap_or(false);
+  let state = handle.state::<AppState>();
+  let settings = state.desktop_settings.lock().unwrap().floating.clone();
+  // Hiding and showing emit window events whose handlers take the tracker lock, so it is released first.
+  let show = {
+    let mut tracker = state.floating.lock().unwrap();
+    let hide = should_auto_hide(&settings, tracker.in_break, tracker.main_focused);
+    if hide && visible {
+      tracker.auto_hidden = true;
+      false
+    } else if !hide && tracker.auto_hidden {
+      tracker.auto_hidden = false;
+      true
+    } else {
+      return;
+    }
+  };
+
+  let _ = if show { window.show() } else { window.hide() };
+}
+
+/// Called by the timer loop whenever the phase changes; `work_started` is set when a work phase begins running.
+pub fn on_timer_phase(handle: &AppHandle, in_break: bool, work_started: bool) {
+  let state = handle.state::<AppState>();
+  let mode = {
+    let mut tracker = state.floating.lock().unwrap();
+    tracker.in_break = in_break;
+    tracker.mode.clone()
+  };
+
+  if work_started && state.desktop_settings.lock().unwrap().floating.show_on_work_start {
+    match handle.get_webview_window("floating") {
+      Some(window) => {
+        if !window.is_visible().unwrap_or(false) {
+          state.floating.lock().unwrap().auto_hidden = true;
+        }
+      }
+      None => report_error(handle, "floating window", build_floating_window(handle, &mode, None, None)),
+    }
+  }
+
+  sync_auto_hide(handle);
+}
+
+/// Tracks focus of the main window for the "hide while the main window is focused" rule.
+pub fn watch_main_window(window: &WebviewWindow) {
+  let handle = window.app_handle().clone();
+  window.on_window_event(move |event| {
+    let focused = match event {
+      WindowEvent::Focused(focused) => *focused,
+      WindowEvent::Destroyed => false,
+      _ => return,
+    };
+    handle.state::<AppState>().floating.lock().unwrap().main_focused = focused;
+    sync_auto_hide(&handle);
+  });
+}
+
+fn track_floating_window(window: &WebviewWindow) {
+  let handle = window.app_handle().clone();
+  window.on_window_event(move |event| match event {
+    WindowEvent::Moved(_) | WindowEvent::Resized(_) => schedule_geometry_save(&handle),
+    WindowEvent::ScaleFactorChanged { .. } => redock_bar(&handle),
+    _ => {}
+  });
+}
+
+fn schedule_geometry_save(handle: &AppHandle) {
+  let generation = {
+    let state = handle.state::<AppState>();
+    let mut tracker = state.floating.lock().unwrap();
+    tracker.generation += 1;
+    tracker.generation
+  };
+  let handle = handle.clone();
+  tauri::async_runtime::spawn(async move {
+    sleep(GEOMETRY_SETTLE_DELAY).await;
+    settle_geometry(&handle, generation);
+  });
+}
+
+/// Saves a move that is still waiting out the settle delay, so quitting right after a drag keeps it.
+pub fn flush_geometry(handle: &AppHandle) {
+  let generation = handle.state::<AppState>().floating.lock().unwrap().generation;
+  settle_geometry(handle, generation);
+}
+
+fn settle_geometry(handle: &AppHandle, generation: u64) {
+  let Some(window) = handle.get_webview_window("floating") else { return; };
+  let state = handle.state::<AppState>();
+  {
+    let tracker = state.floating.lock().unwrap();
+    // The bar's geometry is derived from the monitor, so there is nothing to remember or snap.
+    if tracker.generation != generation || tracker.mode == "bar" { return; }
+  }
+
+  let (Ok(position), Ok(size), Ok(outer), Ok(scale)) = (window.outer_position(), window.inner_size(), window.outer_size(), window.scale_factor()) else { return; };
+  let (screens, layout) = current_screens(handle);
+  let floating_settings = state.desktop_settings.lock().unwrap().floating.clone();
+  let (mut x, mut y) = (position.x, position.y);
+
+  if floating_settings.snap_to_edges {
+    if let Some(screen) = screen_for_window(x, y, outer.width, outer.height, &screens) {
+      let distance = (floating_settings.snap_distance as f64 * scale).round() as i32;
+      (x, y) = snap_to_edges(x, y, outer.width, outer.height, screen, distance);
+      if (x, y) != (position.x, position.y) {
+        let _ = window.set_position(PhysicalPosition::new(x, y));
+      }
+    }
+  }
+
+  let geometry = FloatingGeometry { x, y, width: size.width as f64 / scale, height: size.height as f64 / scale };
+  let mut tracker = state.floating.lock().unwrap();
+  let mode = tracker.mode.clone();
+  tracker.store.remember(&mode, &layout, geometry);
+  report_error(handle, "floating geometry", tracker.save());
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{bar_geometry, clamp_to_screen, monitor_layout_key, normalize_opacity, screen_for_window, should_auto_hide, snap_to_edges, FloatingGeometry, FloatingGeometryStore, ScreenRect};
+  use crate::desktop_settings::{BarEdge, FloatingSettings};
+
+  const LEFT: ScreenRect = ScreenRect { x: 0, y: 0, width: 1920, height: 1040 };
+  const RIGHT: ScreenRect = ScreenRect { x: 1920, y: 0, width: 2560, height: 1400 };
+
+  #[test]
+  fn layout_key_ignores_monitor_order() {
+    assert_eq!(monitor_layout_key(&[LEFT, RIGHT]), monitor_layout_key(&[RIGHT, LEFT]));
+    assert_ne!(monitor_layout_key(&[LEFT, RIGHT]), monitor_layout_key(&[LEFT]));
+  }
+
+  #[test]
+  fn store_falls_back_to_latest_geometry_for_new_layouts() {
+    let mut store = FloatingGeometryStore::default();
+    let geometry = FloatingGeometry { x: 2400, y: 80, width: 312.0, height: 208.0 };
+    store.remember("standard", "dual", geometry);
+
+    assert_eq!(store.lookup("standard", "dual"), Some(geometry));
+    assert_eq!(store.lookup("standard", "laptop"), Some(geometry));
+    assert_eq!(store.lookup("mini", "dual"), None);
+  }
+
+  #[test]
+  fn window_on_removed_monitor_is_pulled_back_onto_first_screen() {
+    let screen = screen_for_window(2400, 80, 312, 208, &[LEFT]).expect("should pick a screen");
+    assert_eq!(screen, LEFT);
+    assert_eq!(clamp_to_screen(2400, 80, 312, 208, screen), (1920 - 312, 80));
+  }
+
+  #[test]
+  fn window_spanning_two_monitors_stays_on_the_larger_overlap() {
+    let screen = screen_for_window(1800, 100, 312, 208, &[LEFT, RIGHT]).expect("should pick a screen");
+    assert_eq!(screen, RIGHT);
+    assert_eq!(clamp_to_screen(1800, 100, 312, 208, screen), (1920, 100));
+  }
+
+  #[test]
+  fn snapping_only_moves_windows_near_an_edge() {
+    assert_eq!(snap_to_edges(10, 500, 312, 208, LEFT, 16), (0, 500));
+    assert_eq!(snap_to_edges(1600, 825, 312, 208, LEFT, 16), (1608, 832));
+    assert_eq!(snap_to_edges(600, 400, 312, 208, LEFT, 16), (600, 400));
+  }
+
+  #[test]
+  fn opacity_is_kept_in_the_visible_range() {
+    assert_eq!(normalize_opacity(0.1), 0.45);
+    assert_eq!(normalize_opacity(1.4), 1.0);
+    assert_eq!(normalize_opacity(f64::NAN), 0.96);
+  }
+
+  #[test]
+  fn auto_hide_rules_are_independent() {
+    let settings = FloatingSettings { hide_during_breaks: true, ..FloatingSettings::default() };
+    assert!(should_auto_hide(&settings, true, false));
+    assert!(!should_auto_hide(&settings, false, true));
+
+    let settings = FloatingSettings { hide_when_main_focused: true, ..FloatingSettings::default() };
+    assert!(should_auto_hide(&settings, false, true));
+    assert!(!should_auto_hide(&settings, true, false));
+  }
+
+  #[test]
+  fn bar_spans_the_work_area_along_its_edge() {
+    assert_eq!(bar_geometry(LEFT, 1.0, BarEdge::Top, 6), (0, 0, 1920, 6));
+    assert_eq!(bar_geometry(RIGHT, 2.0, BarEdge::Bottom, 6), (1920, 1388, 2560, 12));
+    assert_eq!(bar_geometry(RIGHT, 1.0, BarEdge::Right, 6), (4474, 0, 6, 1400));
+  }
+
+  #[test]
+  fn bar_thickness_is_clamped() {
+    assert_eq!(bar_geometry(LEFT, 1.0, BarEdge::Left, 0), (0, 0, 4, 1040));
+    assert_eq!(bar_geometry(LEFT, 1.0, BarEdge::Top, 200).3, 32);
+  }
+}
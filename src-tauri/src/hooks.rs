@@ -0,0 +1,182 @@
+use crate::actions;
+use crate::desktop_settings::{HookEvent, ShellHook};
+use crate::error::AppError;
+use crate::events::{report_error, TimerBus, TimerEvent};
+use crate::{AppState, PomodoroState};
+use serde_json::json;
+use std::process::Stdio;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio::sync::broadcast;
+
+impl HookEvent {
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Self::WorkStarted => "workStarted",
+      Self::WorkFinished => "workFinished",
+      Self::BreakStarted => "breakStarted",
+      Self::BreakFinished => "breakFinished",
+      Self::GoalReached => "goalReached",
+    }
+  }
+}
+
+/// `*Started` fires whenever the timer goes from not running a phase to running it, so resuming after a pause counts too.
+fn started(before: &PomodoroState, after: &PomodoroState) -> Option<HookEvent> {
+  let running = |state: &PomodoroState| state.is_active.then_some(state.mode == "work");
+  match (running(before), running(after)) {
+    (previous, Some(true)) if previous != Some(true) => Some(HookEvent::WorkStarted),
+    (previous, Some(false)) if previous != Some(false) => Some(HookEvent::BreakStarted),
+    _ => None,
+  }
+}
+
+fn shell(command: &str) -> Command {
+  let (program, flag) = if cfg!(target_os = "windows") { ("cmd", "/C") } else { ("sh", "-c") };
+  let mut shell = Command::new(program);
+  shell.arg(flag).arg(command).stdin(Stdio::piped()).stdout(Stdio::null()).stderr(Stdio::piped()).kill_on_drop(true);
+  shell
+}
+
+async fn run_hook(hook: &ShellHook, event: HookEvent, state: &PomodoroState, timeout: Duration) -> Result<(), String> {
+  let mut command = shell(&hook.command);
+  command
+    .env("DAILY_PLANNER_EVENT", event.as_str())
+    .env("DAILY_PLANNER_MODE", &state.mode)
+    .env("DAILY_PLANNER_TIME_LEFT", state.time_left.to_string())
+    .env("DAILY_PLANNER_TASK", state.current_task.as_deref().unwrap_or_default())
+    .env("DAILY_PLANNER_SESSIONS_COMPLETED", state.sessions_completed.to_string());
+  let mut child = command.spawn().map_err(|err| err.to_string())?;
+
+  if let Some(mut stdin) = child.stdin.take() {
+    let payload = json!({ "event": event.as_str(), "state": state });
+    // Hooks that ignore stdin may exit before reading it; a broken pipe is not their failure.
+    let _ = stdin.write_all(format!("{}\n", payload).as_bytes()).await;
+  }
+
+  // Dropping the pending future on timeout kills the child (`kill_on_drop`).
+  let output = tokio::time::timeout(timeout, child.wait_with_output())
+    .await
+    .map_err(|_| format!("timed out after {}s", timeout.as_secs()))?
+    .map_err(|err| err.to_string())?;
+  if output.status.success() {
+    Ok(())
+  } else {
+    Err(format!("{}: {}", output.status, String::from_utf8_lossy(&output.stderr).trim()))
+  }
+}
+
+/// Maps a timer event to the transition it represents, tracking the latest state in `last`.
+pub fn transition(last: &mut PomodoroState, event: TimerEvent) -> Option<(HookEvent, PomodoroState)> {
+  match event {
+    TimerEvent::Tick(state) => {
+      let started = started(last, &state);
+      *last = state;
+      started.map(|event| (event, last.clone()))
+    }
+    TimerEvent::WorkCompleted { .. } => Some((HookEvent::WorkFinished, last.clone())),
+    TimerEvent::BreakCompleted => Some((HookEvent::BreakFinished, last.clone())),
+    TimerEvent::GoalReached { sessions_completed } => Some((HookEvent::GoalReached, PomodoroState { sessions_completed, ..last.clone() })),
+  }
+}
+
+/// Recovers the transitions between `last` and `current` after events were dropped (`RecvError::Lagged`). A phase that
+/// was skipped or reset in the gap reads as finished, and a goal reached in it is not reported.
+pub fn catch_up(last: &mut PomodoroState, current: PomodoroState) -> Vec<(HookEvent, PomodoroState)> {
+  let mut transitions = Vec::new();
+  if last.mode != current.mode {
+    let finished = if last.mode == "work" { HookEvent::WorkFinished } else { HookEvent::BreakFinished };
+    transitions.push((finished, last.clone()));
+  }
+  if let Some(event) = started(last, &current) {
+    transitions.push((event, current.clone()));
+  }
+  *last = current;
+  transitions
+}
+
+/// Runs every hook registered for `event` on its own task, so slow scripts never hold up the timer.
+fn dispatch(handle: &AppHandle, event: HookEvent, state: &PomodoroState) {
+  let hooks = handle.state::<AppState>().desktop_settings.lock().unwrap().hooks.clone();
+  let timeout = Duration::from_secs(hooks.timeout_secs.max(1).into());
+  for hook in hooks.commands.into_iter().filter(|hook| hook.event == event && !hook.command.trim().is_empty()) {
+    let state = state.clone();
+    let handle = handle.clone();
+    tauri::async_runtime::spawn(async move {
+      let result = run_hook(&hook, event, &state, timeout).await;
+      report_error(&handle, "shell hook", result.map_err(|err| AppError::Io(format!("{} hook `{}` failed: {}", event.as_str(), hook.command, err))));
+    });
+  }
+}
+
+async fn run(handle: AppHandle, mut events: broadcast::Receiver<TimerEvent>) {
+  let mut last = actions::pomodoro_state(&handle);
+  loop {
+    let transitions = match events.recv().await {
+      Ok(event) => transition(&mut last, event).into_iter().collect(),
+      Err(broadcast::error::RecvError::Lagged(_)) => catch_up(&mut last, actions::pomodoro_state(&handle)),
+      Err(broadcast::error::RecvError::Closed) => break,
+    };
+    for (event, state) in transitions {
+      dispatch(&handle, event, &state);
+    }
+  }
+}
+
+pub fn spawn_runner(handle: &AppHandle) {
+  let events = handle.state::<TimerBus>().subscribe();
+  tauri::async_runtime::spawn(run(handle.clone(), events));
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{catch_up, started};
+  use crate::desktop_settings::HookEvent;
+  use crate::{PomodoroSettings, PomodoroState};
+
+  fn state(mode: &str, is_active: bool) -> PomodoroState {
+    PomodoroState {
+      time_left: 60,
+      is_active,
+      mode: mode.to_string(),
+      sessions_completed: 0,
+      last_date: "2026-01-01".to_string(),
+      settings: PomodoroSettings::default(),
+      current_task: None,
+    }
+  }
+
+  #[test]
+  fn starting_or_resuming_a_phase_fires_once() {
+    assert_eq!(started(&state("work", false), &state("work", true)), Some(HookEvent::WorkStarted));
+    assert_eq!(started(&state("work", true), &state("work", true)), None);
+    assert_eq!(started(&state("work", true), &state("shortBreak", true)), Some(HookEvent::BreakStarted));
+    assert_eq!(started(&state("shortBreak", true), &state("work", false)), None);
+  }
+
+  #[test]
+  fn catching_up_reports_the_missed_phase_change() {
+    let mut last = state("work", true);
+    let events = catch_up(&mut last, state("shortBreak", true)).into_iter().map(|(event, _)| event).collect::<Vec<_>>();
+    assert_eq!(events, vec![HookEvent::WorkFinished, HookEvent::BreakStarted]);
+    assert_eq!(last.mode, "shortBreak");
+    assert!(catch_up(&mut last, state("shortBreak", true)).is_empty());
+  }
+
+  #[cfg(not(target_os = "windows"))]
+  #[test]
+  fn hooks_get_the_event_and_are_killed_on_timeout() {
+    use super::run_hook;
+    use crate::desktop_settings::ShellHook;
+    use std::time::Duration;
+
+    let hook = ShellHook { event: HookEvent::WorkStarted, command: "test \"$DAILY_PLANNER_EVENT\" = workStarted && grep -q '\"mode\":\"work\"'".to_string() };
+    let runtime = tokio::runtime::Runtime::new().expect("runtime");
+    assert_eq!(runtime.block_on(run_hook(&hook, HookEvent::WorkStarted, &state("work", true), Duration::from_secs(5))), Ok(()));
+
+    let slow = ShellHook { event: HookEvent::WorkStarted, command: "sleep 5".to_string() };
+    assert!(runtime.block_on(run_hook(&slow, HookEvent::WorkStarted, &state("work", true), Duration::from_millis(100))).is_err());
+  }
+}
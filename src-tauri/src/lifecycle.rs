@@ -0,0 +1,65 @@
+use crate::actions;
+use crate::desktop_settings::CloseAction;
+use crate::error::AppResult;
+use crate::events::report_error;
+use crate::{floating, main_window, save_persistent_state, AppState};
+use tauri::{AppHandle, Manager, WebviewWindow, WindowEvent};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+
+/// Applies the `window` desktop settings to closing and minimizing the main window.
+pub fn watch_main_window(window: &WebviewWindow) {
+  let handle = window.app_handle().clone();
+  let watched = window.clone();
+  window.on_window_event(move |event| {
+    let settings = handle.state::<AppState>().desktop_settings.lock().unwrap().window.clone();
+    let result = match event {
+      WindowEvent::CloseRequested { api, .. } => {
+        api.prevent_close();
+        match settings.close_action {
+          CloseAction::HideToTray => watched.hide().map_err(Into::into),
+          CloseAction::Quit => {
+            request_quit(&handle);
+            Ok(())
+          }
+        }
+      }
+      // There is no minimize event; platforms report it as a resize of a window that is now minimized.
+      WindowEvent::Resized(_) if settings.minimize_to_tray && watched.is_minimized().unwrap_or(false) => watched.hide().map_err(Into::into),
+      _ => Ok(()),
+    };
+    report_error(&handle, "main window", result);
+  });
+}
+
+/// Writes what is still only in memory: the timer's session count and window moves that have not settled yet.
+/// Runs on `RunEvent::Exit`, so every way of quitting (tray, closing the window, Cmd+Q, the OS logging out) saves first.
+pub fn flush(handle: &AppHandle) -> AppResult<()> {
+  floating::flush_geometry(handle);
+  main_window::flush_geometry(handle);
+  let state = handle.state::<AppState>();
+  let timer = state.state.lock().unwrap().clone();
+  save_persistent_state(&state.state_path, &timer)
+}
+
+/// Quits, after asking first when a work session is running and the settings want confirmation.
+pub fn request_quit(handle: &AppHandle) {
+  let confirm = handle.state::<AppState>().desktop_settings.lock().unwrap().window.confirm_quit_during_work;
+  let state = actions::pomodoro_state(handle);
+  if !(confirm && state.is_active && state.mode == "work") {
+    handle.exit(0);
+    return;
+  }
+
+  let quit_handle = handle.clone();
+  handle
+    .dialog()
+    .message("当前专注还在进行，退出后计时会停止。")
+    .title("退出 Daily Planner？")
+    .kind(MessageDialogKind::Warning)
+    .buttons(MessageDialogButtons::OkCancelCustom("退出".to_string(), "继续专注".to_string()))
+    .show(move |confirmed| {
+      if confirmed {
+        quit_handle.exit(0);
+      }
+    });
+}
@@ -3,24 +3,62 @@
   windows_subsystem = "windows"
 )]
 
+mod actions;
+mod autostart;
+mod bindings;
+mod cli;
+mod control;
+#[cfg(target_os = "linux")]
+mod dbus;
+mod day;
+mod deep_link;
+mod desktop_settings;
+mod drop_folder;
+mod error;
+mod events;
+// The floating window is replaced by the menu bar status item on macOS.
+#[cfg_attr(target_os = "macos", allow(dead_code))]
+mod floating;
+mod hooks;
+mod lifecycle;
+mod main_window;
+mod mqtt;
+mod native_host;
+mod recurrence;
+mod rollover;
+mod rpc;
+mod shortcuts;
+#[cfg(target_os = "linux")]
+mod status_bar;
+mod webhooks;
+
+use actions::{MainRequest, MainView};
+use desktop_settings::{load_desktop_settings, save_desktop_settings, DesktopSettings, TrayClickAction};
+use error::{AppError, AppResult};
+use events::{emit_day_rolled_over, emit_floating, emit_settings, emit_timer, report_error, FloatingEvent, SettingsEvent, TimerBus, TimerEvent};
+use floating::{apply_window_opacity, build_floating_window, normalize_opacity, resize_floating_window, FloatingMode, FloatingTracker};
 use serde::{Deserialize, Serialize};
+use specta::Type;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 use tauri::{
-  AppHandle, Manager, Emitter,
-  menu::{Menu, MenuItem},
-  tray::{TrayIconBuilder, TrayIconEvent, MouseButton},
+  AppHandle, Manager, WebviewWindow,
+  menu::{Menu, MenuItem, Submenu},
+  tray::{TrayIconBuilder, TrayIconEvent, MouseButton, MouseButtonState},
   image::Image
 };
 #[cfg(all(not(target_os = "macos"), not(target_os = "windows")))]
 use tauri_plugin_notification::NotificationExt;
+#[cfg(not(target_os = "macos"))]
+use tauri::menu::CheckMenuItem;
 #[cfg(any(target_os = "macos", target_os = "windows"))]
 use notify_rust::Notification as NotifyRustNotification;
 use tokio::time::{interval, Duration};
-use chrono::Local;
 
-#[derive(Clone, Serialize, Deserialize, Debug)]
+#[derive(Clone, Serialize, Deserialize, Type, Debug)]
 pub struct PomodoroSettings {
   pub work_duration: u32,
   pub short_break_duration: u32,
@@ -33,7 +71,7 @@ pub struct PomodoroSettings {
   pub stop_after_long_break: bool,
 }
 
-#[derive(Clone, Serialize, Deserialize, Debug)]
+#[derive(Clone, Serialize, Deserialize, Type, Debug)]
 pub struct PomodoroState {
   pub time_left: u32,
   pub is_active: bool,
@@ -53,6 +91,10 @@ pub struct PomodoroPersistentState {
 struct AppState {
   state: Arc<Mutex<PomodoroState>>,
   config_path: PathBuf,
+  state_path: PathBuf,
+  desktop_settings: Arc<Mutex<DesktopSettings>>,
+  desktop_settings_path: PathBuf,
+  floating: Arc<Mutex<FloatingTracker>>,
 }
 
 impl Default for PomodoroSettings {
@@ -78,47 +120,149 @@ fn load_settings(path: &PathBuf) -> PomodoroSettings {
   PomodoroSettings::default()
 }
 
-fn save_settings(path: &PathBuf, settings: &PomodoroSettings) {
-  if let Ok(content) = serde_json::to_string_pretty(settings) { let _ = fs::write(path, content); }
+fn save_settings(path: &PathBuf, settings: &PomodoroSettings) -> AppResult<()> {
+  fs::write(path, serde_json::to_string_pretty(settings)?)?;
+  Ok(())
 }
 
-fn load_persistent_state(path: &PathBuf) -> PomodoroPersistentState {
-  let today = Local::now().format("%Y-%m-%d").to_string();
+fn load_persistent_state(path: &PathBuf, day_start_hour: u32) -> PomodoroPersistentState {
+  let today = day::today(day_start_hour);
   if let Ok(content) = fs::read_to_string(path) {
     if let Ok(p_state) = serde_json::from_str::<PomodoroPersistentState>(&content) {
-      if p_state.last_date == today { return p_state; }
+      if !day::has_rolled_over(&p_state.last_date, &today) { return p_state; }
     }
   }
   PomodoroPersistentState { sessions_completed: 0, last_date: today }
 }
 
-fn save_persistent_state(path: &PathBuf, sessions: u32) {
-  let p_state = PomodoroPersistentState { sessions_completed: sessions, last_date: Local::now().format("%Y-%m-%d").to_string() };
-  if let Ok(content) = serde_json::to_string_pretty(&p_state) { let _ = fs::write(path, content); }
+/// Stores the count under the timer's own date, which only moves forward (see `day::has_rolled_over`).
+fn save_persistent_state(path: &PathBuf, state: &PomodoroState) -> AppResult<()> {
+  let p_state = PomodoroPersistentState { sessions_completed: state.sessions_completed, last_date: state.last_date.clone() };
+  fs::write(path, serde_json::to_string_pretty(&p_state)?)?;
+  Ok(())
 }
 
-fn perform_open_main(handle: &AppHandle) {
+/// Hooks for the config-created main window and for the ones `perform_open_main` rebuilds; runs before the window is shown.
+fn prepare_main_window(window: &WebviewWindow) {
+  floating::watch_main_window(window);
+  lifecycle::watch_main_window(window);
+  main_window::restore(window);
+  main_window::track(window);
+}
+
+fn perform_open_main(handle: &AppHandle) -> AppResult<()> {
   if let Some(window) = handle.get_webview_window("main") {
-    let _ = window.show();
-    let _ = window.unminimize();
-    let _ = window.set_focus();
+    window.show()?;
+    window.unminimize()?;
+    window.set_focus()?;
   } else if let Some(config) = handle.config().app.windows.iter().find(|w| w.label == "main") {
-    if let Ok(window) = tauri::WebviewWindowBuilder::from_config(handle, config).and_then(|builder| builder.build()) {
-      let _ = window.show();
-      let _ = window.set_focus();
+    let window = tauri::WebviewWindowBuilder::from_config(handle, config)?.build()?;
+    prepare_main_window(&window);
+    window.show()?;
+    window.set_focus()?;
+  }
+  Ok(())
+}
+
+fn perform_toggle_timer(handle: &AppHandle) -> AppResult<()> {
+  let state = handle.state::<AppState>();
+  let mut s = state.state.lock().unwrap();
+  let was_active = s.is_active;
+  s.is_active = !s.is_active;
+
+  let notified = if !was_active && s.is_active && s.mode == "work" {
+    show_system_notification(handle, "开始专注", "开始这一轮专注，保持节奏。")
+  } else {
+    Ok(())
+  };
+
+  emit_timer(handle, TimerEvent::Tick(s.clone()))?;
+  notified
+}
+
+fn perform_open_floating(handle: &AppHandle) -> AppResult<()> {
+  #[cfg(target_os = "macos")]
+  {
+    emit_floating(handle, FloatingEvent::StatusBarHint)
+  }
+
+  #[cfg(not(target_os = "macos"))]
+  {
+    if let Some(window) = handle.get_webview_window("floating") {
+      window.show()?;
+      window.set_focus()?;
+      Ok(())
+    } else {
+      build_floating_window(handle, "standard", None, None)
     }
   }
 }
 
-fn show_system_notification(handle: &AppHandle, title: &str, body: &str) {
+fn perform_tray_action(handle: &AppHandle, action: TrayClickAction) -> AppResult<()> {
+  match action {
+    TrayClickAction::None => Ok(()),
+    TrayClickAction::ToggleTimer => perform_toggle_timer(handle),
+    TrayClickAction::OpenMain => perform_open_main(handle),
+    TrayClickAction::OpenFloating => perform_open_floating(handle),
+  }
+}
+
+fn tray_action_for_event(event: &TrayIconEvent, settings: &desktop_settings::TraySettings) -> TrayClickAction {
+  match event {
+    TrayIconEvent::Click { button: MouseButton::Left, button_state: MouseButtonState::Up, .. } => settings.single_click,
+    TrayIconEvent::Click { button: MouseButton::Middle, button_state: MouseButtonState::Up, .. } => settings.middle_click,
+    TrayIconEvent::DoubleClick { button: MouseButton::Left, .. } => settings.double_click,
+    _ => TrayClickAction::None,
+  }
+}
+
+/// Windows delivers both left clicks of a double-click, the second one after `DoubleClick`.
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Holds back the single-click action while a double-click may still follow.
+#[derive(Default)]
+struct TrayClicks {
+  pending_single: AtomicU64,
+  last_double_click: Mutex<Option<Instant>>,
+}
+
+fn handle_tray_click(handle: &AppHandle, event: &TrayIconEvent, settings: &desktop_settings::TraySettings) {
+  let clicks = handle.state::<TrayClicks>();
+  let action = tray_action_for_event(event, settings);
+  match event {
+    TrayIconEvent::DoubleClick { button: MouseButton::Left, .. } => {
+      clicks.pending_single.fetch_add(1, Ordering::SeqCst);
+      *clicks.last_double_click.lock().unwrap() = Some(Instant::now());
+    }
+    TrayIconEvent::Click { button: MouseButton::Left, .. } if action != TrayClickAction::None && settings.double_click != TrayClickAction::None => {
+      if clicks.last_double_click.lock().unwrap().is_some_and(|at| at.elapsed() < DOUBLE_CLICK_INTERVAL) {
+        return;
+      }
+      let click = clicks.pending_single.fetch_add(1, Ordering::SeqCst) + 1;
+      let handle = handle.clone();
+      tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(DOUBLE_CLICK_INTERVAL).await;
+        if handle.state::<TrayClicks>().pending_single.load(Ordering::SeqCst) == click {
+          report_error(&handle, "tray click", perform_tray_action(&handle, action));
+        }
+      });
+      return;
+    }
+    _ => {}
+  }
+  report_error(handle, "tray click", perform_tray_action(handle, action));
+}
+
+/// Desktop backends show notifications asynchronously on Windows and macOS; failures there arrive as `backend_error`.
+fn show_system_notification(handle: &AppHandle, title: &str, body: &str) -> AppResult<()> {
   #[cfg(target_os = "windows")]
   {
     let identifier = handle.config().identifier.clone();
     let title = title.to_string();
     let body = body.to_string();
-    let handle = handle.clone();
+    let report_handle = handle.clone();
 
-    let _ = handle.run_on_main_thread(move || {
+    handle.run_on_main_thread(move || {
       let mut notification = NotifyRustNotification::new();
       notification.summary(&title);
       notification.body(&body);
@@ -133,10 +277,11 @@ fn show_system_notification(handle: &AppHandle, title: &str, body: &str) {
       fallback.summary(&title);
       fallback.body(&body);
       fallback.auto_icon();
-      let _ = fallback.show();
-    });
+      let shown = fallback.show().map(|_| ()).map_err(|err| AppError::Notification(err.to_string()));
+      report_error(&report_handle, "notification", shown);
+    })?;
 
-    return;
+    return Ok(());
   }
 
   #[cfg(target_os = "macos")]
@@ -144,6 +289,7 @@ fn show_system_notification(handle: &AppHandle, title: &str, body: &str) {
     let identifier = handle.config().identifier.clone();
     let title = title.to_string();
     let body = body.to_string();
+    let report_handle = handle.clone();
 
     tauri::async_runtime::spawn(async move {
       let mut notification = NotifyRustNotification::new();
@@ -159,27 +305,31 @@ fn show_system_notification(handle: &AppHandle, title: &str, body: &str) {
         let _ = notify_rust::set_application(&application);
       }
 
-      let result = notification.show();
+      let mut result = notification.show().map(|_| ());
       if result.is_err() && application != "com.apple.Terminal" {
         if notify_rust::set_application("com.apple.Terminal").is_ok() {
           let mut fallback = NotifyRustNotification::new();
           fallback.summary(&title);
           fallback.body(&body);
           fallback.auto_icon();
-          let _ = fallback.show();
+          result = fallback.show().map(|_| ());
         }
       }
+      report_error(&report_handle, "notification", result.map_err(|err| AppError::Notification(err.to_string())));
     });
+
+    Ok(())
   }
 
   #[cfg(all(not(target_os = "macos"), not(target_os = "windows")))]
   {
-    let _ = handle
+    handle
       .notification()
       .builder()
       .title(title)
       .body(body)
-      .show();
+      .show()
+      .map_err(|err| AppError::Notification(err.to_string()))
   }
 }
 
@@ -208,7 +358,7 @@ fn truncate_for_tray(task: &str, max_chars: usize) -> String {
   }
 }
 
-#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+#[cfg_attr(target_os = "windows", allow(dead_code))]
 fn format_tray_text(mode: &str, time_left: u32, current_task: Option<&str>) -> String {
   let mode_str = if mode == "work" { "专注" } else { "休息" };
   let time_str = format!("{:02}:{:02}", time_left / 60, time_left % 60);
@@ -247,28 +397,6 @@ fn format_tray_tooltip(mode: &str, time_left: u32, current_task: Option<&str>) -
   format!("{}: {}", mode_str, time_str)
 }
 
-fn floating_window_spec(mode: &str) -> (&'static str, f64, f64, bool) {
-  if mode == "mini" {
-    ("/?view=floating&mode=mini", 232.0, 56.0, true)
-  } else {
-    ("/?view=floating", 312.0, 208.0, true)
-  }
-}
-
-fn preferred_floating_size(mode: &str, width: Option<f64>, height: Option<f64>) -> (f64, f64) {
-  let (_, default_width, default_height, _) = floating_window_spec(mode);
-  let (min_width, min_height, max_width, max_height) = if mode == "mini" {
-    (208.0, 56.0, 320.0, 120.0)
-  } else {
-    (292.0, 188.0, 560.0, 360.0)
-  };
-
-  (
-    width.unwrap_or(default_width).clamp(min_width, max_width),
-    height.unwrap_or(default_height).clamp(min_height, max_height),
-  )
-}
-
 fn legacy_daily_planner_ai_dir() -> Option<PathBuf> {
   std::env::var_os("APPDATA")
     .map(PathBuf::from)
@@ -417,60 +545,50 @@ fn read_legacy_native_store_value(name: &str) -> Option<String> {
 }
 
 #[tauri::command]
-fn open_main(handle: AppHandle) { perform_open_main(&handle); }
+#[specta::specta]
+async fn open_main(view: Option<MainView>, handle: AppHandle) -> AppResult<()> {
+  match view {
+    Some(view) => actions::navigate(&handle, view),
+    None => perform_open_main(&handle),
+  }
+}
 
 #[tauri::command]
-fn get_pomodoro_state(state: tauri::State<'_, AppState>) -> PomodoroState {
-  state.state.lock().unwrap().clone()
+#[specta::specta]
+fn get_pomodoro_state(handle: AppHandle) -> AppResult<PomodoroState> {
+  Ok(actions::pomodoro_state(&handle))
 }
 
 #[tauri::command]
-fn update_task_name(name: Option<String>, state: tauri::State<'_, AppState>, handle: AppHandle) {
-  let mut s = state.state.lock().unwrap();
-  s.current_task = name;
-  let _ = handle.emit("pomodoro_tick", s.clone());
+#[specta::specta]
+fn update_task_name(name: Option<String>, handle: AppHandle) -> AppResult<()> {
+  actions::set_task_name(&handle, name)
 }
 
 #[tauri::command]
-fn toggle_timer(state: tauri::State<'_, AppState>, handle: AppHandle) {
-  let mut s = state.state.lock().unwrap();
-  let was_active = s.is_active;
-  s.is_active = !s.is_active;
-
-  if !was_active && s.is_active && s.mode == "work" {
-    show_system_notification(&handle, "开始专注", "开始这一轮专注，保持节奏。");
-  }
-
-  let _ = handle.emit("pomodoro_tick", s.clone());
+#[specta::specta]
+fn toggle_timer(handle: AppHandle) -> AppResult<()> {
+  perform_toggle_timer(&handle)
 }
 
 #[tauri::command]
-fn reset_timer(state: tauri::State<'_, AppState>, handle: AppHandle) {
-  let mut s = state.state.lock().unwrap();
-  s.is_active = false;
-  s.time_left = match s.mode.as_str() {
-    "shortBreak" => s.settings.short_break_duration.max(1) * 60,
-    "longBreak" => s.settings.long_break_duration.max(1) * 60,
-    _ => s.settings.work_duration.max(1) * 60,
-  };
-  let _ = handle.emit("pomodoro_tick", s.clone());
+#[specta::specta]
+fn reset_timer(handle: AppHandle) -> AppResult<()> {
+  actions::reset(&handle)
 }
 
 #[tauri::command]
-fn skip_mode(state: tauri::State<'_, AppState>, handle: AppHandle) {
-  let mut s = state.state.lock().unwrap();
-  let (next_mode, next_time_left) = next_mode_after_skip(&s.mode, s.sessions_completed, &s.settings);
-  s.mode = next_mode;
-  s.time_left = next_time_left;
-  s.is_active = false;
-  let _ = handle.emit("pomodoro_tick", s.clone());
+#[specta::specta]
+fn skip_mode(handle: AppHandle) -> AppResult<()> {
+  actions::skip(&handle)
 }
 
 #[tauri::command]
-fn update_settings(settings: PomodoroSettings, state: tauri::State<'_, AppState>, handle: AppHandle) {
+#[specta::specta]
+fn update_settings(settings: PomodoroSettings, state: tauri::State<'_, AppState>, handle: AppHandle) -> AppResult<()> {
   let mut s = state.state.lock().unwrap();
   s.settings = settings.clone();
-  save_settings(&state.config_path, &s.settings);
+  let saved = save_settings(&state.config_path, &s.settings);
   if !s.is_active {
     s.time_left = match s.mode.as_str() {
       "shortBreak" => s.settings.short_break_duration.max(1) * 60,
@@ -478,96 +596,69 @@ fn update_settings(settings: PomodoroSettings, state: tauri::State<'_, AppState>
       _ => s.settings.work_duration.max(1) * 60,
     };
   }
-  let _ = handle.emit("pomodoro_tick", s.clone());
+  emit_timer(&handle, TimerEvent::Tick(s.clone()))?;
+  emit_settings(&handle, SettingsEvent::PomodoroChanged(settings))?;
+  saved
 }
 
 #[tauri::command]
-fn show_notification(title: String, body: String, handle: AppHandle) {
-  show_system_notification(&handle, &title, &body);
+#[specta::specta]
+fn show_notification(title: String, body: String, handle: AppHandle) -> AppResult<()> {
+  show_system_notification(&handle, &title, &body)
 }
 
 #[tauri::command]
-async fn toggle_floating_window(handle: tauri::AppHandle, mode: Option<String>, width: Option<f64>, height: Option<f64>) {
+#[specta::specta]
+async fn toggle_floating_window(handle: tauri::AppHandle, mode: Option<FloatingMode>, width: Option<f64>, height: Option<f64>) -> AppResult<()> {
   #[cfg(target_os = "macos")]
   {
-    let _ = handle.emit("mac_statusbar_hint", ());
-    return;
+    return emit_floating(&handle, FloatingEvent::StatusBarHint);
   }
 
   #[cfg(not(target_os = "macos"))]
   {
     if let Some(window) = handle.get_webview_window("floating") {
-      let _ = window.close();
+      window.close()?;
+      Ok(())
     } else {
-      let safe_mode = mode.unwrap_or_else(|| "standard".to_string());
-      let safe_mode = if safe_mode == "mini" { "mini" } else { "standard" };
-      let (url, _, _, resizable) = floating_window_spec(safe_mode);
-      let (width, height) = preferred_floating_size(safe_mode, width, height);
-      let _ = tauri::WebviewWindowBuilder::new(
-        &handle,
-        "floating",
-        tauri::WebviewUrl::App(url.into())
-      )
-      .title("Floating")
-      .inner_size(width, height)
-      .resizable(resizable)
-      .decorations(false)
-      .transparent(false)
-      .always_on_top(true)
-      .skip_taskbar(true)
-      .build();
+      let safe_mode = mode.unwrap_or(FloatingMode::Standard).as_str();
+      build_floating_window(&handle, safe_mode, width, height)
     }
   }
 }
 
 #[tauri::command]
-async fn open_floating_mode(handle: tauri::AppHandle, mode: String, width: Option<f64>, height: Option<f64>) {
+#[specta::specta]
+async fn open_floating_mode(handle: tauri::AppHandle, mode: FloatingMode, width: Option<f64>, height: Option<f64>) -> AppResult<()> {
   #[cfg(target_os = "macos")]
   {
-    let _ = handle.emit("mac_statusbar_hint", ());
-    return;
+    return emit_floating(&handle, FloatingEvent::StatusBarHint);
   }
 
   #[cfg(not(target_os = "macos"))]
   {
-  let safe_mode = if mode == "mini" { "mini" } else { "standard" };
-  let (url, _, _, resizable) = floating_window_spec(safe_mode);
-  let (width, height) = preferred_floating_size(safe_mode, width, height);
+  let safe_mode = mode.as_str();
   if let Some(window) = handle.get_webview_window("floating") {
-    let _ = window.set_size(tauri::Size::Logical(tauri::LogicalSize { width, height }));
-    let _ = window.set_resizable(resizable);
-    let _ = window.eval(&format!(
-      "window.localStorage.setItem('floating-pomodoro-mode', '{}'); window.dispatchEvent(new CustomEvent('floating-mode-changed', {{ detail: '{}' }}));",
-      safe_mode, safe_mode
-    ));
-    let _ = window.show();
-    let _ = window.set_focus();
+    resize_floating_window(&window, safe_mode, width, height)?;
+    emit_floating(&handle, FloatingEvent::ModeChanged { mode })?;
+    window.show()?;
+    window.set_focus()?;
+    Ok(())
   } else {
-    let _ = tauri::WebviewWindowBuilder::new(
-      &handle,
-      "floating",
-      tauri::WebviewUrl::App(url.into())
-    )
-    .title("Floating")
-    .inner_size(width, height)
-    .resizable(resizable)
-    .decorations(false)
-    .transparent(false)
-    .always_on_top(true)
-    .skip_taskbar(true)
-    .build();
+    build_floating_window(&handle, safe_mode, width, height)
   }
   }
 }
 
 #[tauri::command]
-async fn open_floating_settings(handle: tauri::AppHandle) {
+#[specta::specta]
+async fn open_floating_settings(handle: tauri::AppHandle) -> AppResult<()> {
   if let Some(window) = handle.get_webview_window("floating-settings") {
-    let _ = window.show();
-    let _ = window.unminimize();
-    let _ = window.set_focus();
+    window.show()?;
+    window.unminimize()?;
+    window.set_focus()?;
   } else {
-    let _ = tauri::WebviewWindowBuilder::new(
+    tauri::WebviewWindowBuilder::new(
       &handle,
       "floating-settings",
       tauri::WebviewUrl::App("/?view=floating-settings".into())
@@ -578,38 +669,182 @@ async fn open_floating_settings(handle: tauri::AppHandle) {
     .decorations(true)
     .always_on_top(true)
     .skip_taskbar(true)
-    .build();
+    .build()?;
   }
+  Ok(())
 }
 
 #[tauri::command]
-fn get_runtime_platform() -> String {
-  std::env::consts::OS.to_string()
+#[specta::specta]
+fn get_runtime_platform() -> AppResult<String> {
+  Ok(std::env::consts::OS.to_string())
 }
 
 #[tauri::command]
-fn broadcast_floating_preferences(theme: String, opacity: f64, handle: AppHandle) {
-  let _ = handle.emit("floating_preferences_changed", serde_json::json!({
-    "theme": theme,
-    "opacity": opacity
-  }));
+#[specta::specta]
+fn broadcast_floating_preferences(theme: String, opacity: f64, state: tauri::State<'_, AppState>, handle: AppHandle) -> AppResult<()> {
+  let opacity = normalize_opacity(opacity);
+  {
+    let mut desktop_settings = state.desktop_settings.lock().unwrap();
+    if desktop_settings.floating.opacity != opacity {
+      desktop_settings.floating.opacity = opacity;
+      save_desktop_settings(&state.desktop_settings_path, &desktop_settings)?;
+    }
+  }
+  if let Some(window) = handle.get_webview_window("floating") {
+    apply_window_opacity(&window, opacity);
+  }
+  emit_floating(&handle, FloatingEvent::PreferencesChanged { theme, opacity })
+}
+
+#[tauri::command]
+#[specta::specta]
+fn set_floating_click_through(enabled: bool, handle: AppHandle) -> AppResult<()> {
+  floating::set_click_through(&handle, enabled)
+}
+
+#[tauri::command]
+#[specta::specta]
+fn get_desktop_settings(state: tauri::State<'_, AppState>) -> AppResult<DesktopSettings> {
+  Ok(state.desktop_settings.lock().unwrap().clone())
+}
+
+#[tauri::command]
+#[specta::specta]
+fn set_mqtt_password(password: String, handle: AppHandle) -> AppResult<()> {
+  mqtt::store_password(&password)?;
+  mqtt::sync(&handle);
+  Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+fn update_desktop_settings(mut settings: DesktopSettings, state: tauri::State<'_, AppState>, handle: AppHandle) -> AppResult<()> {
+  if settings.day.start_hour > 23 {
+    return Err(AppError::Serialization(format!("day start hour {} is not between 0 and 23", settings.day.start_hour)));
+  }
+  settings.floating.opacity = normalize_opacity(settings.floating.opacity);
+  let (previous, saved) = {
+    let mut current = state.desktop_settings.lock().unwrap();
+    let previous = std::mem::replace(&mut *current, settings.clone());
+    (previous, save_desktop_settings(&state.desktop_settings_path, &current))
+  };
+
+  if let Some(tray) = handle.tray_by_id("main") {
+    tray.set_show_menu_on_left_click(settings.tray.single_click == TrayClickAction::None)?;
+    if !settings.tray.show_countdown {
+      tray.set_title(None::<&str>)?;
+    }
+  }
+  if let Some(window) = handle.get_webview_window("floating") {
+    apply_window_opacity(&window, settings.floating.opacity);
+  }
+  floating::redock_bar(&handle);
+  floating::sync_auto_hide(&handle);
+  // Restarting drops connected clients, so background services only restart when their own settings changed.
+  if previous.rpc != settings.rpc {
+    rpc::sync(&handle);
+  }
+  if previous.mqtt != settings.mqtt {
+    mqtt::sync(&handle);
+  }
+  if previous.drop_folder != settings.drop_folder {
+    drop_folder::sync(&handle);
+  }
+  if previous.webhooks != settings.webhooks {
+    webhooks::sync(&handle);
+  }
+  let autostarted = if previous.autostart != settings.autostart { autostart::sync(&handle) } else { Ok(()) };
+  #[cfg(target_os = "linux")]
+  if previous.status_bar != settings.status_bar {
+    status_bar::sync(&handle);
+  }
+  let registered = if previous.shortcuts != settings.shortcuts { shortcuts::apply(&handle, &settings.shortcuts) } else { Ok(()) };
+
+  emit_settings(&handle, SettingsEvent::DesktopChanged(settings))?;
+  // Settings are applied even when the write fails; the error only means they will not survive a restart.
+  registered.and(autostarted).and(saved)
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn open_capture_window(handle: AppHandle) -> AppResult<()> {
+  actions::open_capture_window(&handle)
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn submit_capture(text: String, handle: AppHandle) -> AppResult<()> {
+  actions::capture_text(&handle, text)
+}
+
+#[tauri::command]
+#[specta::specta]
+fn take_main_requests(handle: AppHandle) -> AppResult<Vec<MainRequest>> {
+  actions::take_main_requests(&handle)
+}
+
+#[tauri::command]
+#[specta::specta]
+fn roll_over_tasks(handle: AppHandle, tasks: Vec<serde_json::Map<String, serde_json::Value>>, last_date: Option<String>, calendar: recurrence::WorkdayCalendar) -> AppResult<Option<rollover::RolledOverTasks>> {
+  rollover::roll_over_tasks(&handle, tasks, last_date.as_deref(), &calendar)
+}
+
+#[tauri::command]
+#[specta::specta]
+fn expand_recurrence(rule: recurrence::RecurrenceRule, start: String, from: String, to: String, calendar: recurrence::WorkdayCalendar) -> AppResult<Vec<recurrence::Occurrence>> {
+  let occurrences = recurrence::expand(&rule, &start, &from, &to, &calendar, &chrono::Local)?;
+  Ok(occurrences.iter().map(recurrence::to_occurrence).collect())
 }
 
 #[tauri::command]
-fn load_legacy_daily_planner_ai_store() -> Option<String> {
-  read_legacy_daily_planner_ai_store_json()
+#[specta::specta]
+fn parse_recurrence_rule(rule: String) -> AppResult<recurrence::RecurrenceRule> {
+  rule.parse()
 }
 
 #[tauri::command]
-fn load_legacy_native_store_value(name: String) -> Option<String> {
-  read_legacy_native_store_value(&name)
+#[specta::specta]
+fn load_legacy_daily_planner_ai_store() -> AppResult<Option<String>> {
+  Ok(read_legacy_daily_planner_ai_store_json())
+}
+
+#[tauri::command]
+#[specta::specta]
+fn load_legacy_native_store_value(name: String) -> AppResult<Option<String>> {
+  Ok(read_legacy_native_store_value(&name))
 }
 
 fn main() {
+  let args = std::env::args().skip(1).collect::<Vec<_>>();
+  if native_host::is_host_launch(&args) {
+    std::process::exit(native_host::run());
+  }
+  #[cfg(target_os = "linux")]
+  if args.first().map(String::as_str) == Some("install-native-host") {
+    std::process::exit(native_host::install(&args[1..]));
+  }
+  match cli::parse_cli_args(&args) {
+    Some(Ok(invocation)) => std::process::exit(cli::run(invocation)),
+    Some(Err(message)) => {
+      eprintln!("daily-planner: {}\n{}", message, cli::USAGE);
+      std::process::exit(64);
+    }
+    None => {}
+  }
+
+  let specta_builder = bindings::builder();
+  let invoke_handler = specta_builder.invoke_handler();
+
   let app = tauri::Builder::default()
-    .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
-      perform_open_main(app);
+    .plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
+      if !deep_link::handle_args(app, &args) && !autostart::launched_minimized(&args) {
+        report_error(app, "open main window", perform_open_main(app));
+      }
     }))
+    .plugin(tauri_plugin_deep_link::init())
+    .plugin(tauri_plugin_autostart::init(tauri_plugin_autostart::MacosLauncher::LaunchAgent, Some(vec![autostart::MINIMIZED_ARG])))
+    .plugin(tauri_plugin_global_shortcut::Builder::new().with_handler(|app, shortcut, event| shortcuts::on_shortcut(app, shortcut, event)).build())
     .plugin(tauri_plugin_shell::init())
     .plugin(tauri_plugin_notification::init())
     .plugin(tauri_plugin_process::init())
@@ -617,7 +852,8 @@ fn main() {
     .plugin(tauri_plugin_updater::Builder::new().build())
     .plugin(tauri_plugin_fs::init())
     .plugin(tauri_plugin_dialog::init())
-    .setup(|app| {
+    .setup(move |app| {
+      specta_builder.mount_events(app);
       let handle = app.handle().clone();
       let resource_path = handle.path().resource_dir().expect("Failed res dir");
       
@@ -630,52 +866,119 @@ fn main() {
 
       let config_path = get_config_path(&handle).join("pomodoro_settings.json");
       let state_path = get_config_path(&handle).join("pomodoro_state.json");
+      let desktop_settings_path = get_config_path(&handle).join("desktop_settings.json");
       let settings = load_settings(&config_path);
-      let p_state = load_persistent_state(&state_path);
+      let desktop_settings = load_desktop_settings(&desktop_settings_path);
+      report_error(&handle, "MQTT password", mqtt::migrate_plaintext_password(&desktop_settings_path, &desktop_settings));
+      let show_menu_on_left_click = desktop_settings.tray.single_click == TrayClickAction::None;
+      let day_start_hour = desktop_settings.day.start_hour;
+      let desktop_settings_ptr = Arc::new(Mutex::new(desktop_settings));
+      let floating_tracker = FloatingTracker::load(get_config_path(&handle).join("floating_geometry.json"));
+      let p_state = load_persistent_state(&state_path, day_start_hour);
       
       let initial_state = PomodoroState { time_left: settings.work_duration.max(1) * 60, is_active: false, mode: "work".to_string(), sessions_completed: p_state.sessions_completed, last_date: p_state.last_date, settings, current_task: None };
       let state_ptr = Arc::new(Mutex::new(initial_state));
-      app.manage(AppState { state: state_ptr.clone(), config_path });
+      app.manage(AppState { state: state_ptr.clone(), config_path, state_path: state_path.clone(), desktop_settings: desktop_settings_ptr.clone(), desktop_settings_path, floating: Arc::new(Mutex::new(floating_tracker)) });
+      app.manage(actions::MainRequestQueue::load(get_config_path(&handle).join("main_requests.json")));
+      app.manage(TimerBus::default());
+      app.manage(TrayClicks::default());
+      app.manage(rpc::RpcServer::new(get_config_path(&handle).join("rpc_endpoint.json")));
+      app.manage(mqtt::MqttBridge::default());
+      app.manage(main_window::MainWindowTracker::load(get_config_path(&handle).join("main_window.json")));
+      app.manage(drop_folder::DropFolderWatcher::default());
+      #[cfg(target_os = "linux")]
+      app.manage(status_bar::StatusBarWriter::default());
 
       let show_i = MenuItem::with_id(app, "show", "显示主界面", true, None::<&str>).unwrap();
+      let toggle_i = MenuItem::with_id(app, "toggle_timer", "开始 / 暂停专注", true, None::<&str>).unwrap();
+      let floating_i = MenuItem::with_id(app, "floating", "打开悬浮窗", true, None::<&str>).unwrap();
+      let capture_i = MenuItem::with_id(app, "capture", "快速记录到收件箱", true, None::<&str>).unwrap();
+      let open_inbox_i = MenuItem::with_id(app, "open_inbox", "收件箱", true, None::<&str>).unwrap();
+      let open_today_i = MenuItem::with_id(app, "open_today", "今日", true, None::<&str>).unwrap();
+      let open_review_i = MenuItem::with_id(app, "open_review", "复盘", true, None::<&str>).unwrap();
+      let open_calendar_i = MenuItem::with_id(app, "open_calendar", "日历", true, None::<&str>).unwrap();
+      let open_view_i = Submenu::with_items(app, "打开到", true, &[&open_inbox_i, &open_today_i, &open_review_i, &open_calendar_i]).unwrap();
       let quit_i = MenuItem::with_id(app, "quit", "退出应用", true, None::<&str>).unwrap();
-      let menu = Menu::with_items(app, &[&show_i, &quit_i]).unwrap();
+      let menu = Menu::with_items(app, &[&show_i, &open_view_i, &toggle_i, &floating_i, &capture_i, &quit_i]).unwrap();
+      // The menu bar status item stands in for the floating window on macOS, so there is nothing to click through.
+      #[cfg(not(target_os = "macos"))]
+      {
+        let click_through_i = CheckMenuItem::with_id(app, "floating_click_through", "悬浮窗点击穿透", true, false, None::<&str>).unwrap();
+        menu.insert(&click_through_i, 5).unwrap();
+        handle.state::<AppState>().floating.lock().unwrap().click_through_item = Some(click_through_i);
+      }
+      if let Some(main_window) = app.get_webview_window("main") {
+        prepare_main_window(&main_window);
+        // The window is created hidden so a `--minimized` launch never flashes it.
+        if !autostart::launched_minimized(&args) {
+          main_window.show()?;
+        }
+      }
 
       let _tray = TrayIconBuilder::with_id("main")
         .icon(app.default_window_icon().unwrap().clone())
         .menu(&menu)
+        .show_menu_on_left_click(show_menu_on_left_click)
         .on_menu_event(|app_handle, event| {
-          if event.id.as_ref() == "show" { perform_open_main(app_handle); }
-          else if event.id.as_ref() == "quit" { app_handle.exit(0); }
+          let result = match event.id.as_ref() {
+            "show" => perform_open_main(app_handle),
+            "toggle_timer" => perform_toggle_timer(app_handle),
+            "floating" => perform_open_floating(app_handle),
+            "capture" => actions::open_capture_window(app_handle),
+            "floating_click_through" => floating::set_click_through(app_handle, !floating::is_click_through(app_handle)),
+            "quit" => {
+              lifecycle::request_quit(app_handle);
+              Ok(())
+            }
+            id => match id.strip_prefix("open_").and_then(MainView::from_name) {
+              Some(view) => actions::navigate(app_handle, view),
+              None => Ok(()),
+            },
+          };
+          report_error(app_handle, "tray menu", result);
         })
         .on_tray_icon_event(|tray, event| {
-          if let TrayIconEvent::DoubleClick { button: MouseButton::Left, .. } = event {
-            perform_open_main(tray.app_handle());
-          }
+          let handle = tray.app_handle();
+          let tray_settings = handle.state::<AppState>().desktop_settings.lock().unwrap().tray.clone();
+          handle_tray_click(handle, &event, &tray_settings);
         })
         .build(app).expect("Failed to build tray");
 
       let state_ptr_timer = state_ptr.clone();
+      let desktop_settings_timer = desktop_settings_ptr.clone();
       tauri::async_runtime::spawn(async move {
         let mut interval = interval(Duration::from_secs(1));
         let mut last_mode = String::new();
+        #[cfg(not(target_os = "macos"))]
+        let (mut last_in_break, mut last_work_running) = (false, false);
         loop {
           interval.tick().await;
-          let mut s = state_ptr_timer.lock().unwrap();
-          let today = Local::now().format("%Y-%m-%d").to_string();
-          if s.last_date != today {
-            s.last_date = today;
-            s.sessions_completed = 0;
-            save_persistent_state(&state_path, s.sessions_completed);
+          let day_start_hour = desktop_settings_timer.lock().unwrap().day.start_hour;
+          let today = day::today(day_start_hour);
+          let rolled_over_from = {
+            let mut s = state_ptr_timer.lock().unwrap();
+            day::has_rolled_over(&s.last_date, &today).then(|| {
+              s.sessions_completed = 0;
+              let from = std::mem::replace(&mut s.last_date, today.clone());
+              report_error(&handle, "timer state", save_persistent_state(&state_path, &s));
+              // The count reset also has to reach listeners while the timer is paused and no tick follows.
+              report_error(&handle, "timer", emit_timer(&handle, TimerEvent::Tick(s.clone())));
+              from
+            })
+          };
+          // Announced outside the lock: the main window answers with `roll_over_tasks`.
+          if let Some(from) = rolled_over_from {
+            report_error(&handle, "day rollover", emit_day_rolled_over(&handle, Some(from), today, None));
           }
+          let mut s = state_ptr_timer.lock().unwrap();
           if s.is_active {
             if s.time_left > 0 { s.time_left -= 1; }
             else {
               s.is_active = false;
               if s.mode == "work" {
-                let _ = handle.emit("pomodoro_completed", s.settings.work_duration);
+                report_error(&handle, "timer", emit_timer(&handle, TimerEvent::WorkCompleted { work_duration: s.settings.work_duration }));
                 s.sessions_completed += 1;
-                save_persistent_state(&state_path, s.sessions_completed);
+                report_error(&handle, "timer state", save_persistent_state(&state_path, &s));
                 
                 let is_long = s.sessions_completed % s.settings.long_break_interval == 0;
                 s.mode = (if is_long { "longBreak" } else { "shortBreak" }).to_string();
@@ -683,14 +986,15 @@ fn main() {
                 
                 if s.settings.stop_after_sessions > 0 && s.sessions_completed >= s.settings.stop_after_sessions { 
                   s.is_active = false; 
-                  show_system_notification(&handle, "目标达成", "今天的番茄目标已经完成。");
+                  report_error(&handle, "timer", emit_timer(&handle, TimerEvent::GoalReached { sessions_completed: s.sessions_completed }));
+                  report_error(&handle, "notification", show_system_notification(&handle, "目标达成", "今天的番茄目标已经完成。"));
                 } else {
                    s.is_active = s.settings.auto_start_breaks;
-                   show_system_notification(&handle, "专注结束", "这一轮专注已完成，起来活动一下。");
+                   report_error(&handle, "notification", show_system_notification(&handle, "专注结束", "这一轮专注已完成，起来活动一下。"));
                 }
               } else {
                 let was_long = s.mode == "longBreak";
-                let _ = handle.emit("break_completed", ());
+                report_error(&handle, "timer", emit_timer(&handle, TimerEvent::BreakCompleted));
                 if was_long && s.settings.stop_after_long_break {
                   // Bug Fix: Reset to work mode properly
                   s.is_active = false; 
@@ -703,22 +1007,25 @@ fn main() {
                    s.is_active = s.settings.auto_start_pomodoros;
                 }
                 if s.mode == "work" && s.is_active {
-                  show_system_notification(&handle, "开始专注", "休息结束，开始下一轮专注。");
+                  report_error(&handle, "notification", show_system_notification(&handle, "开始专注", "休息结束，开始下一轮专注。"));
                 } else {
-                  show_system_notification(&handle, "休息结束", "休息完成，可以准备进入下一轮专注。");
+                  report_error(&handle, "notification", show_system_notification(&handle, "休息结束", "休息完成，可以准备进入下一轮专注。"));
                 }
               }
             }
-            let _ = handle.emit("pomodoro_tick", s.clone());
+            report_error(&handle, "timer", emit_timer(&handle, TimerEvent::Tick(s.clone())));
           }
 
           if let Some(tray) = handle.tray_by_id("main") {
             let tray_tooltip = format_tray_tooltip(&s.mode, s.time_left, s.current_task.as_deref());
             
-            #[cfg(target_os = "macos")]
+            // Windows trays have no text slot; macOS shows a menu bar title and Linux an indicator label.
+            #[cfg(not(target_os = "windows"))]
             {
-              let tray_text = format_tray_text(&s.mode, s.time_left, s.current_task.as_deref());
-              let _ = tray.set_title(Some(tray_text));
+              if desktop_settings_timer.lock().unwrap().tray.show_countdown {
+                let tray_text = format_tray_text(&s.mode, s.time_left, s.current_task.as_deref());
+                let _ = tray.set_title(Some(tray_text));
+              }
             }
             let _ = tray.set_tooltip(Some(tray_tooltip));
 
@@ -729,19 +1036,56 @@ fn main() {
               last_mode = s.mode.clone();
             }
           }
+
+          #[cfg(not(target_os = "macos"))]
+          {
+            let in_break = s.mode != "work";
+            let work_running = s.mode == "work" && s.is_active;
+            drop(s);
+            if in_break != last_in_break || work_running != last_work_running {
+              floating::on_timer_phase(&handle, in_break, work_running && !last_work_running);
+              (last_in_break, last_work_running) = (in_break, work_running);
+            }
+          }
         }
       });
+
+      let launch_handle = app.handle().clone();
+      control::spawn_server(&launch_handle);
+      rpc::sync(&launch_handle);
+      mqtt::sync(&launch_handle);
+      drop_folder::sync(&launch_handle);
+      report_error(&launch_handle, "launch at login", autostart::sync(&launch_handle));
+      hooks::spawn_runner(&launch_handle);
+      let shortcut_settings = desktop_settings_ptr.lock().unwrap().shortcuts.clone();
+      report_error(&launch_handle, "global shortcuts", shortcuts::apply(&launch_handle, &shortcut_settings));
+      webhooks::spawn_worker(&launch_handle, get_config_path(&launch_handle).join("webhook_outbox.json"));
+      #[cfg(target_os = "linux")]
+      {
+        status_bar::sync(&launch_handle);
+        dbus::spawn_service(&launch_handle);
+      }
+      deep_link::handle_args(&launch_handle, &args);
+      // macOS delivers scheme URLs as open-URL events instead of argv.
+      #[cfg(target_os = "macos")]
+      {
+        use tauri_plugin_deep_link::DeepLinkExt;
+        let url_handle = launch_handle.clone();
+        app.deep_link().on_open_url(move |event| {
+          deep_link::handle_args(&url_handle, event.urls().iter().map(|url| url.to_string()));
+        });
+      }
       Ok(())
     })
-    .invoke_handler(tauri::generate_handler![get_pomodoro_state, toggle_timer, reset_timer, skip_mode, update_settings, open_main, show_notification, toggle_floating_window, open_floating_mode, open_floating_settings, get_runtime_platform, broadcast_floating_preferences, update_task_name, load_legacy_daily_planner_ai_store, load_legacy_native_store_value])
+    .invoke_handler(invoke_handler)
     .build(tauri::generate_context!())
     .expect("error");
 
-  app.run(|_app_handle, _event| {
+  app.run(|app_handle, event| match event {
+    tauri::RunEvent::Exit => report_error(app_handle, "saving before quit", lifecycle::flush(app_handle)),
     #[cfg(target_os = "macos")]
-    if let tauri::RunEvent::Reopen { .. } = _event {
-      perform_open_main(_app_handle);
-    }
+    tauri::RunEvent::Reopen { .. } => report_error(app_handle, "open main window", perform_open_main(app_handle)),
+    _ => {}
   });
 }
 
@@ -0,0 +1,176 @@
+use crate::error::AppResult;
+use crate::events::report_error;
+use crate::floating::{clamp_to_screen, monitor_work_area, screen_for_window, ScreenRect};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, LogicalSize, Manager, PhysicalPosition, WebviewWindow, WindowEvent};
+use tokio::time::{sleep, Duration};
+
+/// Same settle delay as the floating window: a drag is one burst of moves.
+const SAVE_DELAY: Duration = Duration::from_millis(400);
+
+/// Outer position in physical pixels, inner size in logical pixels, like `FloatingGeometry`.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct MainGeometry {
+  pub x: i32,
+  pub y: i32,
+  pub width: f64,
+  pub height: f64,
+  /// Position and size are the ones to return to when the window is unmaximized.
+  pub maximized: bool,
+  /// Monitor name as reported by the platform; restoring prefers it while it is connected.
+  pub monitor: Option<String>,
+}
+
+struct Tracked {
+  geometry: Option<MainGeometry>,
+  generation: u64,
+}
+
+/// Last known geometry of the main window, persisted to `main_window.json`.
+pub struct MainWindowTracker {
+  path: PathBuf,
+  tracked: Mutex<Tracked>,
+}
+
+impl MainWindowTracker {
+  pub fn load(path: PathBuf) -> Self {
+    let geometry = fs::read_to_string(&path).ok().and_then(|content| serde_json::from_str::<MainGeometry>(&content).ok());
+    Self { path, tracked: Mutex::new(Tracked { geometry, generation: 0 }) }
+  }
+
+  fn save(&self, geometry: &MainGeometry) -> AppResult<()> {
+    fs::write(&self.path, serde_json::to_string_pretty(geometry)?)?;
+    Ok(())
+  }
+}
+
+/// The saved monitor's work area when it is still connected, otherwise the one showing most of the saved rectangle.
+fn target_screen(geometry: &MainGeometry, screens: &[(Option<String>, ScreenRect, f64)]) -> Option<(ScreenRect, f64)> {
+  let named = geometry.monitor.as_ref().and_then(|name| screens.iter().find(|(monitor, _, _)| monitor.as_ref() == Some(name)));
+  let (_, screen, scale) = named.or_else(|| {
+    let rects = screens.iter().map(|(_, screen, _)| *screen).collect::<Vec<_>>();
+    let width = (geometry.width * screens.first()?.2).round() as u32;
+    let height = (geometry.height * screens.first()?.2).round() as u32;
+    let screen = screen_for_window(geometry.x, geometry.y, width, height, &rects)?;
+    screens.iter().find(|(_, rect, _)| *rect == screen)
+  })?;
+  Some((*screen, *scale))
+}
+
+/// Physical `(x, y)` and logical `(width, height)` that keep the window fully on `screen`.
+fn fit_to_screen(geometry: &MainGeometry, screen: ScreenRect, scale: f64) -> (i32, i32, f64, f64) {
+  let width = geometry.width.min(screen.width as f64 / scale);
+  let height = geometry.height.min(screen.height as f64 / scale);
+  let (x, y) = clamp_to_screen(geometry.x, geometry.y, (width * scale).round() as u32, (height * scale).round() as u32, screen);
+  (x, y, width, height)
+}
+
+/// Moves a main window that has not been shown yet to where it was last time.
+pub fn restore(window: &WebviewWindow) {
+  let tracker = window.app_handle().state::<MainWindowTracker>();
+  let Some(geometry) = tracker.tracked.lock().unwrap().geometry.clone() else { return; };
+  let screens = window
+    .available_monitors()
+    .unwrap_or_default()
+    .iter()
+    .map(|monitor| (monitor.name().cloned(), monitor_work_area(monitor), monitor.scale_factor()))
+    .collect::<Vec<_>>();
+  let Some((screen, scale)) = target_screen(&geometry, &screens) else { return; };
+
+  let (x, y, width, height) = fit_to_screen(&geometry, screen, scale);
+  let _ = window.set_size(LogicalSize::new(width, height));
+  let _ = window.set_position(PhysicalPosition::new(x, y));
+  if geometry.maximized {
+    let _ = window.maximize();
+  }
+}
+
+fn capture(window: &WebviewWindow, previous: Option<&MainGeometry>) -> Option<MainGeometry> {
+  // Minimized windows report placeholder positions (-32000 on Windows); keep what was there before.
+  if window.is_minimized().unwrap_or(false) {
+    return None;
+  }
+  let maximized = window.is_maximized().unwrap_or(false);
+  let monitor = window.current_monitor().ok().flatten().and_then(|monitor| monitor.name().cloned());
+  let (position, size, scale) = (window.outer_position().ok()?, window.inner_size().ok()?, window.scale_factor().ok()?);
+  match previous {
+    // A maximized window's rectangle is the monitor's; keep the normal one for unmaximizing.
+    Some(previous) if maximized => Some(MainGeometry { maximized, monitor, ..previous.clone() }),
+    _ => Some(MainGeometry { x: position.x, y: position.y, width: size.width as f64 / scale, height: size.height as f64 / scale, maximized, monitor }),
+  }
+}
+
+fn settle(window: &WebviewWindow, generation: u64) {
+  let handle = window.app_handle();
+  let tracker = handle.state::<MainWindowTracker>();
+  let mut tracked = tracker.tracked.lock().unwrap();
+  if tracked.generation != generation {
+    return;
+  }
+  let Some(geometry) = capture(window, tracked.geometry.as_ref()) else { return; };
+  if tracked.geometry.as_ref() == Some(&geometry) {
+    return;
+  }
+  report_error(handle, "main window geometry", tracker.save(&geometry));
+  tracked.geometry = Some(geometry);
+}
+
+/// Saves a move or resize that is still waiting out the save delay, so quitting right after it keeps it.
+pub fn flush_geometry(handle: &AppHandle) {
+  let Some(window) = handle.get_webview_window("main") else { return; };
+  let generation = handle.state::<MainWindowTracker>().tracked.lock().unwrap().generation;
+  settle(&window, generation);
+}
+
+/// Persists the window's geometry once a move or resize settles.
+pub fn track(window: &WebviewWindow) {
+  let watched = window.clone();
+  window.on_window_event(move |event| {
+    if !matches!(event, WindowEvent::Moved(_) | WindowEvent::Resized(_)) {
+      return;
+    }
+    let generation = {
+      let tracker = watched.app_handle().state::<MainWindowTracker>();
+      let mut tracked = tracker.tracked.lock().unwrap();
+      tracked.generation += 1;
+      tracked.generation
+    };
+    let window = watched.clone();
+    tauri::async_runtime::spawn(async move {
+      sleep(SAVE_DELAY).await;
+      settle(&window, generation);
+    });
+  });
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{fit_to_screen, target_screen, MainGeometry};
+  use crate::floating::ScreenRect;
+
+  fn geometry(x: i32, y: i32, monitor: Option<&str>) -> MainGeometry {
+    MainGeometry { x, y, width: 1200.0, height: 800.0, maximized: false, monitor: monitor.map(str::to_string) }
+  }
+
+  #[test]
+  fn restoring_prefers_the_named_monitor_while_it_is_connected() {
+    let laptop = ScreenRect { x: 0, y: 0, width: 2880, height: 1750 };
+    let external = ScreenRect { x: 2880, y: 0, width: 2560, height: 1400 };
+    let screens = vec![(Some("Built-in".to_string()), laptop, 2.0), (Some("DELL U2720Q".to_string()), external, 1.0)];
+
+    assert_eq!(target_screen(&geometry(3000, 100, Some("DELL U2720Q")), &screens), Some((external, 1.0)));
+    // The external monitor is unplugged: fall back to the screen the rectangle overlaps, or the primary one.
+    assert_eq!(target_screen(&geometry(3000, 100, Some("DELL U2720Q")), &screens[..1]), Some((laptop, 2.0)));
+    assert_eq!(target_screen(&geometry(100, 100, Some("Projector")), &screens), Some((laptop, 2.0)));
+  }
+
+  #[test]
+  fn windows_larger_than_the_screen_are_shrunk_and_kept_on_it() {
+    let small = ScreenRect { x: 0, y: 0, width: 1366, height: 728 };
+    assert_eq!(fit_to_screen(&geometry(900, 500, None), small, 1.0), (166, 0, 1200.0, 728.0));
+    assert_eq!(fit_to_screen(&geometry(-50, 20, None), ScreenRect { x: 0, y: 0, width: 2560, height: 1400 }, 1.0), (0, 20, 1200.0, 800.0));
+  }
+}
@@ -0,0 +1,432 @@
+use crate::actions;
+use crate::cli::{self, CliCommand};
+use crate::desktop_settings::{save_desktop_settings, DesktopSettings, MqttSettings};
+use crate::error::{AppError, AppResult};
+use crate::events::{report_error, TimerBus, TimerEvent};
+use crate::{AppState, PomodoroState};
+use rumqttc::{AsyncClient, Event, LastWill, MqttOptions, Packet, QoS};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Manager};
+use tokio::sync::broadcast;
+
+/// Wait between reconnect attempts; `rumqttc` reconnects on the next poll after an error.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Keyring entry of the broker password, so it is neither written to `desktop_settings.json` nor sent to the webviews.
+const KEYRING_SERVICE: &str = "daily-planner";
+const KEYRING_USER: &str = "mqtt-broker";
+
+fn keyring_entry() -> AppResult<keyring::Entry> {
+  keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER).map_err(|err| AppError::Io(err.to_string()))
+}
+
+fn load_password() -> AppResult<String> {
+  match keyring_entry()?.get_password() {
+    Err(keyring::Error::NoEntry) => Ok(String::new()),
+    result => result.map_err(|err| AppError::Io(err.to_string())),
+  }
+}
+
+/// Saves the broker password; an empty one removes it.
+pub fn store_password(password: &str) -> AppResult<()> {
+  let entry = keyring_entry()?;
+  let result = if password.is_empty() { entry.delete_credential() } else { entry.set_password(password) };
+  match result {
+    Err(keyring::Error::NoEntry) => Ok(()),
+    result => result.map_err(|err| AppError::Io(err.to_string())),
+  }
+}
+
+/// Moves a password that older versions saved in plain text into the keyring and rewrites the settings file without it.
+pub fn migrate_plaintext_password(path: &Path, settings: &DesktopSettings) -> AppResult<()> {
+  let Ok(content) = fs::read_to_string(path) else { return Ok(()); };
+  let Some(password) = serde_json::from_str::<Value>(&content)?.pointer("/mqtt/password").and_then(Value::as_str).map(str::to_string) else {
+    return Ok(());
+  };
+  store_password(&password)?;
+  save_desktop_settings(&path.to_path_buf(), settings)
+}
+
+/// Handle of the running bridge, so changing the connection settings restarts it.
+#[derive(Default)]
+pub struct MqttBridge(Mutex<Option<JoinHandle<()>>>);
+
+struct Topics {
+  state: String,
+  availability: String,
+  set: String,
+}
+
+impl Topics {
+  fn new(profile: &str) -> Self {
+    let profile = profile.trim();
+    // `+`, `#` and `/` would change what the topic matches; keep the profile a single plain level.
+    let profile = profile.replace(['+', '#', '/'], "_");
+    let base = format!("daily-planner/{}", if profile.is_empty() { "default" } else { &profile });
+    Self { state: format!("{}/state", base), availability: format!("{}/availability", base), set: format!("{}/set", base) }
+  }
+}
+
+fn options(settings: &MqttSettings, password: &str, topics: &Topics) -> MqttOptions {
+  let client_id = format!("daily-planner-{}", uuid::Uuid::new_v4().simple());
+  let mut options = MqttOptions::new(client_id, settings.host.trim(), settings.port);
+  options.set_keep_alive(Duration::from_secs(30));
+  if !settings.username.is_empty() {
+    options.set_credentials(settings.username.clone(), password);
+  }
+  // Retained, so Home Assistant shows the timer as unavailable after a crash or a lost connection.
+  options.set_last_will(LastWill::new(&topics.availability, "offline", QoS::AtLeastOnce, true));
+  options
+}
+
+fn state_payload(state: &PomodoroState) -> String {
+  json!({
+    "mode": state.mode,
+    "is_active": state.is_active,
+    "time_left": state.time_left,
+    "task": state.current_task,
+    "sessions_completed": state.sessions_completed,
+  })
+  .to_string()
+}
+
+/// Accepts the control socket's JSON (`{"command":"start","minutes":50}`) or the same words as the CLI (`pause`, `add buy milk`).
+fn parse_command(payload: &[u8]) -> Result<CliCommand, String> {
+  let text = std::str::from_utf8(payload).map_err(|err| err.to_string())?.trim();
+  if text.starts_with('{') {
+    return serde_json::from_str(text).map_err(|err| err.to_string());
+  }
+  let words = text.split_whitespace().map(str::to_string).collect::<Vec<_>>();
+  match cli::parse_cli_args(&words) {
+    Some(Ok(invocation)) => Ok(invocation.command),
+    Some(Err(err)) => Err(err),
+    None => Err(format!("unknown command: {}", text)),
+  }
+}
+
+/// What the bridge reads, drives and reports to; the app implements it on `AppHandle`, tests on a plain state.
+trait BridgeHost: Send + Sync {
+  fn state(&self) -> PomodoroState;
+  fn execute(&self, command: CliCommand) -> AppResult<()>;
+  fn report(&self, context: &str, result: AppResult<()>);
+}
+
+impl BridgeHost for AppHandle {
+  fn state(&self) -> PomodoroState {
+    actions::pomodoro_state(self)
+  }
+
+  fn execute(&self, command: CliCommand) -> AppResult<()> {
+    cli::execute(self, command).map(|_| ())
+  }
+
+  fn report(&self, context: &str, result: AppResult<()>) {
+    report_error(self, context, result);
+  }
+}
+
+fn publish_state(client: &AsyncClient, topics: &Topics, state: &PomodoroState) {
+  // Never block on a full request queue: the event loop is polled by this same task, and a newer state will follow.
+  let _ = client.try_publish(&topics.state, QoS::AtMostOnce, true, state_payload(state));
+}
+
+async fn run(handle: AppHandle, settings: MqttSettings, events: broadcast::Receiver<TimerEvent>) {
+  // The keyring may wait on a locked Secret Service, so it is read off the async workers.
+  let password = match tauri::async_runtime::spawn_blocking(load_password).await {
+    Ok(Ok(password)) => password,
+    Ok(Err(err)) => {
+      report_error(&handle, "MQTT password", Err(err));
+      String::new()
+    }
+    Err(_) => String::new(),
+  };
+  bridge(&handle, &settings, &password, events).await;
+}
+
+/// Publishes the timer until the event bus closes and, with `accept_commands`, runs the commands sent to the `set` topic.
+async fn bridge(host: &dyn BridgeHost, settings: &MqttSettings, password: &str, mut events: broadcast::Receiver<TimerEvent>) {
+  let topics = Topics::new(&settings.profile);
+  let (client, mut connection) = AsyncClient::new(options(settings, password, &topics), 16);
+  // Only the first error of an outage is reported; the loop keeps retrying quietly.
+  let mut failing = false;
+
+  loop {
+    tokio::select! {
+      polled = connection.poll() => match polled {
+        Ok(Event::Incoming(Packet::ConnAck(_))) => {
+          failing = false;
+          let _ = client.try_publish(&topics.availability, QoS::AtLeastOnce, true, "online");
+          publish_state(&client, &topics, &host.state());
+          if settings.accept_commands {
+            let _ = client.try_subscribe(&topics.set, QoS::AtLeastOnce);
+          }
+        }
+        // The broker only delivers `set` after the subscription above; the check guards against a stale session.
+        Ok(Event::Incoming(Packet::Publish(message))) if message.topic == topics.set && settings.accept_commands => {
+          let result = parse_command(&message.payload).map_err(AppError::Io).and_then(|command| host.execute(command));
+          host.report("MQTT command", result);
+        }
+        Ok(_) => {}
+        Err(err) => {
+          if !failing {
+            failing = true;
+            host.report("MQTT connection", Err(AppError::Io(err.to_string())));
+          }
+          tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+      },
+      event = events.recv() => match event {
+        Ok(TimerEvent::Tick(state)) => publish_state(&client, &topics, &state),
+        Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+        Err(broadcast::error::RecvError::Closed) => break,
+      },
+    }
+  }
+}
+
+/// Starts, restarts or stops the bridge to match the `mqtt` desktop setting.
+pub fn sync(handle: &AppHandle) {
+  let settings = handle.state::<AppState>().desktop_settings.lock().unwrap().mqtt.clone();
+  let bridge = handle.state::<MqttBridge>();
+  let mut running = bridge.0.lock().unwrap();
+
+  if let Some(task) = running.take() {
+    task.abort();
+  }
+  if !settings.enabled || settings.host.trim().is_empty() {
+    return;
+  }
+
+  let events = handle.state::<TimerBus>().subscribe();
+  *running = Some(tauri::async_runtime::spawn(run(handle.clone(), settings, events)));
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{bridge, options, parse_command, state_payload, BridgeHost, Topics};
+  use crate::cli::CliCommand;
+  use crate::desktop_settings::MqttSettings;
+  use crate::error::AppResult;
+  use crate::events::TimerEvent;
+  use crate::{PomodoroSettings, PomodoroState};
+  use rumqttc::{AsyncClient, Event, EventLoop, Packet, QoS};
+  use std::process::{Child, Command, Stdio};
+  use std::sync::{Arc, Mutex};
+  use std::time::Duration;
+  use tokio::sync::broadcast;
+
+  #[test]
+  fn topics_keep_the_profile_to_one_level() {
+    let topics = Topics::new("desk/+#");
+    assert_eq!(topics.state, "daily-planner/desk___/state");
+    assert_eq!(Topics::new(" ").set, "daily-planner/default/set");
+  }
+
+  #[test]
+  fn commands_accept_json_or_cli_words() {
+    assert_eq!(parse_command(br#"{"command":"start","task":null,"minutes":50}"#), Ok(CliCommand::Start { task: None, minutes: Some(50) }));
+    assert_eq!(parse_command(b"pause\n"), Ok(CliCommand::Pause));
+    assert_eq!(parse_command(b"add buy milk"), Ok(CliCommand::Add { text: "buy milk".to_string() }));
+    assert!(parse_command(b"explode").is_err());
+  }
+
+  struct FakeTimer(Mutex<PomodoroState>);
+
+  impl BridgeHost for FakeTimer {
+    fn state(&self) -> PomodoroState {
+      self.0.lock().unwrap().clone()
+    }
+
+    fn execute(&self, command: CliCommand) -> AppResult<()> {
+      let mut state = self.0.lock().unwrap();
+      match command {
+        CliCommand::Pause => state.is_active = false,
+        CliCommand::Start { .. } => state.is_active = true,
+        _ => {}
+      }
+      Ok(())
+    }
+
+    fn report(&self, context: &str, result: AppResult<()>) {
+      result.unwrap_or_else(|err| panic!("{}: {}", context, err));
+    }
+  }
+
+  fn running_work() -> PomodoroState {
+    PomodoroState {
+      time_left: 90,
+      is_active: true,
+      mode: "work".to_string(),
+      sessions_completed: 1,
+      last_date: "2026-01-01".to_string(),
+      settings: PomodoroSettings::default(),
+      current_task: Some("Write report".to_string()),
+    }
+  }
+
+  /// The next message on a subscribed topic, as JSON.
+  async fn next_payload(connection: &mut EventLoop) -> serde_json::Value {
+    let message = tokio::time::timeout(Duration::from_secs(5), async {
+      loop {
+        if let Event::Incoming(Packet::Publish(message)) = connection.poll().await.expect("subscriber connection") {
+          break message;
+        }
+      }
+    })
+    .await
+    .expect("state message");
+    serde_json::from_slice(&message.payload).expect("json state")
+  }
+
+  /// Sends `pause` to the bridge's `set` topic for a while, since the bridge subscribes only after connecting, and
+  /// returns whether the timer paused.
+  async fn pause_over_mqtt(settings: &MqttSettings, timer: &FakeTimer) -> bool {
+    let topics = Topics::new(&settings.profile);
+    let (commander, mut commander_loop) = AsyncClient::new(options(settings, "", &topics), 16);
+    tokio::spawn(async move { while commander_loop.poll().await.is_ok() {} });
+    for _ in 0..10 {
+      commander.publish(&topics.set, QoS::AtLeastOnce, false, "pause").await.expect("queue command");
+      tokio::time::sleep(Duration::from_millis(200)).await;
+      if !timer.state().is_active {
+        return true;
+      }
+    }
+    false
+  }
+
+  /// A throwaway broker on a free port.
+  struct Broker {
+    process: Child,
+    port: u16,
+  }
+
+  impl Broker {
+    fn start() -> Self {
+      let port = std::net::TcpListener::bind("127.0.0.1:0").and_then(|listener| listener.local_addr()).expect("free port").port();
+      let process = Command::new("mosquitto")
+        .args(["-p", &port.to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("start mosquitto (install the mosquitto package)");
+      std::thread::sleep(Duration::from_millis(300));
+      Self { process, port }
+    }
+  }
+
+  impl Drop for Broker {
+    fn drop(&mut self) {
+      let _ = self.process.kill();
+      let _ = self.process.wait();
+    }
+  }
+
+  /// Run by the `integration` CI job: `cargo test -- --ignored mqtt::`.
+  #[test]
+  #[ignore = "needs mosquitto"]
+  fn state_is_retained_on_a_local_mosquitto() {
+    let broker = Broker::start();
+    let settings = MqttSettings { host: "127.0.0.1".to_string(), port: broker.port, profile: "test".to_string(), ..MqttSettings::default() };
+    let topics = Topics::new(&settings.profile);
+    let state = PomodoroState {
+      time_left: 90,
+      is_active: true,
+      mode: "work".to_string(),
+      sessions_completed: 1,
+      last_date: "2026-01-01".to_string(),
+      settings: PomodoroSettings::default(),
+      current_task: Some("Write report".to_string()),
+    };
+
+    let runtime = tokio::runtime::Runtime::new().expect("runtime");
+    runtime.block_on(async {
+      let (publisher, mut publisher_loop) = AsyncClient::new(options(&settings, "", &topics), 16);
+      publisher.publish(&topics.state, QoS::AtLeastOnce, true, state_payload(&state)).await.expect("queue publish");
+      loop {
+        if let Event::Incoming(Packet::PubAck(_)) = publisher_loop.poll().await.expect("publisher connection") {
+          break;
+        }
+      }
+
+      // A client that connects later still gets the state, because it was retained.
+      let (subscriber, mut subscriber_loop) = AsyncClient::new(options(&settings, "", &topics), 16);
+      subscriber.subscribe(&topics.state, QoS::AtLeastOnce).await.expect("queue subscribe");
+      let received = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+          if let Event::Incoming(Packet::Publish(message)) = subscriber_loop.poll().await.expect("subscriber connection") {
+            break message;
+          }
+        }
+      })
+      .await
+      .expect("retained state");
+      assert!(received.retain);
+      let payload: serde_json::Value = serde_json::from_slice(&received.payload).expect("json state");
+      assert_eq!(payload["task"], "Write report");
+      assert_eq!(payload["time_left"], 90);
+    });
+  }
+
+  #[test]
+  #[ignore = "needs mosquitto"]
+  fn bridge_publishes_ticks_and_runs_set_commands() {
+    let broker = Broker::start();
+    let settings = MqttSettings { host: "127.0.0.1".to_string(), port: broker.port, profile: "bridge".to_string(), accept_commands: true, ..MqttSettings::default() };
+    let topics = Topics::new(&settings.profile);
+    let timer = Arc::new(FakeTimer(Mutex::new(running_work())));
+
+    let runtime = tokio::runtime::Runtime::new().expect("runtime");
+    runtime.block_on(async {
+      let (subscriber, mut subscriber_loop) = AsyncClient::new(options(&settings, "", &topics), 16);
+      subscriber.subscribe(&topics.state, QoS::AtLeastOnce).await.expect("queue subscribe");
+      let (events, receiver) = broadcast::channel(8);
+      let bridge_task = {
+        let (timer, settings) = (timer.clone(), settings.clone());
+        tokio::spawn(async move { bridge(&*timer, &settings, "", receiver).await })
+      };
+
+      // Published on connecting.
+      assert_eq!(next_payload(&mut subscriber_loop).await["time_left"], 90);
+      let mut ticked = running_work();
+      ticked.time_left = 89;
+      events.send(TimerEvent::Tick(ticked)).expect("send tick");
+      let payload = next_payload(&mut subscriber_loop).await;
+      assert_eq!(payload["time_left"], 89);
+      assert_eq!(payload["task"], "Write report");
+
+      assert!(pause_over_mqtt(&settings, &timer).await, "set command was not run");
+      drop(events);
+      bridge_task.await.expect("bridge stops with the event bus");
+    });
+  }
+
+  #[test]
+  #[ignore = "needs mosquitto"]
+  fn bridge_ignores_set_commands_unless_accepted() {
+    let broker = Broker::start();
+    let settings = MqttSettings { host: "127.0.0.1".to_string(), port: broker.port, profile: "readonly".to_string(), ..MqttSettings::default() };
+    let topics = Topics::new(&settings.profile);
+    let timer = Arc::new(FakeTimer(Mutex::new(running_work())));
+
+    let runtime = tokio::runtime::Runtime::new().expect("runtime");
+    runtime.block_on(async {
+      let (subscriber, mut subscriber_loop) = AsyncClient::new(options(&settings, "", &topics), 16);
+      subscriber.subscribe(&topics.state, QoS::AtLeastOnce).await.expect("queue subscribe");
+      let (events, receiver) = broadcast::channel(8);
+      let bridge_task = {
+        let (timer, settings) = (timer.clone(), settings.clone());
+        tokio::spawn(async move { bridge(&*timer, &settings, "", receiver).await })
+      };
+      next_payload(&mut subscriber_loop).await;
+
+      assert!(!pause_over_mqtt(&settings, &timer).await, "set command ran without accept_commands");
+      drop(events);
+      bridge_task.await.expect("bridge stops with the event bus");
+    });
+  }
+}
@@ -0,0 +1,234 @@
+use crate::cli::CliCommand;
+use crate::control;
+use crate::error::AppError;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+/// Name the browser extension passes to `runtime.connectNative`; also the manifest file name.
+pub const HOST_NAME: &str = "com.lqf0624.dailyplanner";
+/// Chromium refuses replies over 1 MiB; requests are held to the same limit since a capture is a title and a URL.
+const MAX_MESSAGE: usize = 1024 * 1024;
+
+/// One request from the extension: a control-socket command plus an optional `id` echoed in the reply.
+#[derive(Deserialize, Debug, PartialEq)]
+struct HostMessage {
+  #[serde(default)]
+  id: Value,
+  #[serde(flatten)]
+  command: CliCommand,
+}
+
+/// Browsers start the host with the extension's origin (Chromium) or the manifest path and extension ID (Firefox).
+pub fn is_host_launch(args: &[String]) -> bool {
+  match args {
+    [origin, ..] if origin.starts_with("chrome-extension://") => true,
+    [manifest, _extension_id] => Path::new(manifest).file_name().and_then(|name| name.to_str()) == Some(format!("{}.json", HOST_NAME).as_str()),
+    _ => false,
+  }
+}
+
+/// Reads one length-prefixed message; `None` once the browser closes the port.
+fn read_message(input: &mut impl Read) -> io::Result<Option<Vec<u8>>> {
+  let mut length = [0u8; 4];
+  match input.read_exact(&mut length) {
+    Ok(()) => {}
+    Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+    Err(err) => return Err(err),
+  }
+  let length = u32::from_ne_bytes(length) as usize;
+  if length > MAX_MESSAGE {
+    return Err(io::Error::new(io::ErrorKind::InvalidData, format!("message of {} bytes is too large", length)));
+  }
+  let mut message = vec![0u8; length];
+  input.read_exact(&mut message)?;
+  Ok(Some(message))
+}
+
+fn write_message(output: &mut impl Write, message: &Value) -> io::Result<()> {
+  let bytes = serde_json::to_vec(message)?;
+  output.write_all(&(bytes.len() as u32).to_ne_bytes())?;
+  output.write_all(&bytes)?;
+  output.flush()
+}
+
+/// Forwards the request to the running app; the reply carries the timer state or an `AppError`.
+fn reply(message: &[u8]) -> Value {
+  let request = match serde_json::from_slice::<HostMessage>(message) {
+    Ok(request) => request,
+    Err(err) => return json!({ "id": Value::Null, "ok": false, "error": AppError::from(err) }),
+  };
+  match control::request(&request.command) {
+    Ok(Ok(state)) => json!({ "id": request.id, "ok": true, "state": state }),
+    Ok(Err(err)) => json!({ "id": request.id, "ok": false, "error": err }),
+    Err(err) => json!({ "id": request.id, "ok": false, "error": AppError::Io(format!("the app is not running ({})", err)) }),
+  }
+}
+
+/// Serves the extension until it disconnects. Returns the process exit code.
+pub fn run() -> i32 {
+  let mut input = io::stdin().lock();
+  let mut output = io::stdout().lock();
+  loop {
+    let result = match read_message(&mut input) {
+      Ok(Some(message)) => write_message(&mut output, &reply(&message)),
+      Ok(None) => return 0,
+      Err(err) => Err(err),
+    };
+    if let Err(err) = result {
+      // stdout belongs to the browser; stderr ends up in its log.
+      eprintln!("daily-planner native host: {}", err);
+      return 1;
+    }
+  }
+}
+
+#[cfg(target_os = "linux")]
+pub const INSTALL_USAGE: &str = "usage: daily-planner install-native-host [--firefox-id ID] [--chromium-id ID]";
+
+/// Per-user manifest locations for every browser that has an extension ID.
+#[cfg(target_os = "linux")]
+fn manifests(home: &Path, config: &Path, exe: &Path, firefox_id: Option<&str>, chromium_id: Option<&str>) -> Vec<(std::path::PathBuf, Value)> {
+  let file_name = format!("{}.json", HOST_NAME);
+  let manifest = |allowed: (&str, Value)| {
+    let mut manifest = json!({ "name": HOST_NAME, "description": "Daily Planner", "path": exe, "type": "stdio" });
+    manifest[allowed.0] = allowed.1;
+    manifest
+  };
+  let mut manifests = Vec::new();
+  if let Some(id) = firefox_id {
+    manifests.push((home.join(".mozilla/native-messaging-hosts").join(&file_name), manifest(("allowed_extensions", json!([id])))));
+  }
+  if let Some(id) = chromium_id {
+    let origins = json!([format!("chrome-extension://{}/", id)]);
+    for browser in ["google-chrome", "chromium"] {
+      manifests.push((config.join(browser).join("NativeMessagingHosts").join(&file_name), manifest(("allowed_origins", origins.clone()))));
+    }
+  }
+  manifests
+}
+
+/// `daily-planner install-native-host`: writes the host manifests pointing at this binary. Returns the process exit code.
+#[cfg(target_os = "linux")]
+pub fn install(args: &[String]) -> i32 {
+  let mut firefox_id = None;
+  let mut chromium_id = None;
+  let mut args = args.iter();
+  while let Some(arg) = args.next() {
+    let target = match arg.as_str() {
+      "--firefox-id" => &mut firefox_id,
+      "--chromium-id" => &mut chromium_id,
+      other => {
+        eprintln!("daily-planner: unexpected argument: {}\n{}", other, INSTALL_USAGE);
+        return 64;
+      }
+    };
+    match args.next().map(|id| id.trim()).filter(|id| !id.is_empty()) {
+      Some(id) => *target = Some(id.to_string()),
+      None => {
+        eprintln!("daily-planner: {} needs a value\n{}", arg, INSTALL_USAGE);
+        return 64;
+      }
+    }
+  }
+  if firefox_id.is_none() && chromium_id.is_none() {
+    eprintln!("daily-planner: pass the extension ID for at least one browser\n{}", INSTALL_USAGE);
+    return 64;
+  }
+
+  let Some(home) = std::env::var_os("HOME").map(std::path::PathBuf::from) else {
+    eprintln!("daily-planner: HOME is not set");
+    return 1;
+  };
+  let config = std::env::var_os("XDG_CONFIG_HOME").map(std::path::PathBuf::from).unwrap_or_else(|| home.join(".config"));
+  let exe = match std::env::current_exe() {
+    Ok(exe) => exe,
+    Err(err) => {
+      eprintln!("daily-planner: cannot locate this binary: {}", err);
+      return 1;
+    }
+  };
+
+  let mut code = 0;
+  for (path, manifest) in manifests(&home, &config, &exe, firefox_id.as_deref(), chromium_id.as_deref()) {
+    let written = path
+      .parent()
+      .map_or(Ok(()), std::fs::create_dir_all)
+      .and_then(|_| std::fs::write(&path, serde_json::to_string_pretty(&manifest).unwrap_or_default()));
+    match written {
+      Ok(()) => println!("{}", path.display()),
+      Err(err) => {
+        eprintln!("daily-planner: {}: {}", path.display(), err);
+        code = 1;
+      }
+    }
+  }
+  code
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{is_host_launch, read_message, write_message, HostMessage, HOST_NAME};
+  use crate::cli::CliCommand;
+  use serde_json::{json, Value};
+  use std::io::Cursor;
+
+  fn args(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+  }
+
+  #[test]
+  fn browser_launches_are_recognised() {
+    assert!(is_host_launch(&args(&["chrome-extension://abcdefghijklmnop/"])));
+    assert!(is_host_launch(&args(&["chrome-extension://abcdefghijklmnop/", "--parent-window=0"])));
+    let manifest = format!("/home/me/.mozilla/native-messaging-hosts/{}.json", HOST_NAME);
+    assert!(is_host_launch(&args(&[&manifest, "planner@example.org"])));
+    assert!(!is_host_launch(&args(&["add", "buy milk"])));
+    assert!(!is_host_launch(&args(&["daily-planner://open/today"])));
+  }
+
+  #[test]
+  fn messages_round_trip_with_a_length_prefix() {
+    let mut buffer = Vec::new();
+    write_message(&mut buffer, &json!({ "command": "toggle", "id": 7 })).unwrap();
+    assert_eq!(u32::from_ne_bytes(buffer[..4].try_into().unwrap()) as usize, buffer.len() - 4);
+
+    let mut input = Cursor::new(buffer);
+    let message = read_message(&mut input).unwrap().expect("one message");
+    assert_eq!(serde_json::from_slice::<HostMessage>(&message).unwrap(), HostMessage { id: json!(7), command: CliCommand::Toggle });
+    assert!(read_message(&mut input).unwrap().is_none());
+  }
+
+  #[test]
+  fn oversized_messages_are_rejected() {
+    let mut input = Cursor::new((64 * 1024 * 1024u32).to_ne_bytes().to_vec());
+    assert!(read_message(&mut input).is_err());
+  }
+
+  #[test]
+  fn clip_requests_need_no_id() {
+    let request = serde_json::from_str::<HostMessage>(r#"{"command":"clip","title":"Rust book","url":"https://doc.rust-lang.org/book/"}"#).unwrap();
+    assert_eq!(request.id, Value::Null);
+    assert_eq!(request.command, CliCommand::Clip { title: "Rust book".to_string(), url: Some("https://doc.rust-lang.org/book/".to_string()) });
+  }
+
+  #[cfg(target_os = "linux")]
+  #[test]
+  fn manifests_are_written_per_browser() {
+    use std::path::Path;
+    let manifests = super::manifests(Path::new("/home/me"), Path::new("/home/me/.config"), Path::new("/usr/bin/daily-planner"), Some("planner@example.org"), Some("abcdefghijklmnop"));
+    let paths = manifests.iter().map(|(path, _)| path.to_string_lossy().to_string()).collect::<Vec<_>>();
+    assert_eq!(
+      paths,
+      vec![
+        format!("/home/me/.mozilla/native-messaging-hosts/{}.json", HOST_NAME),
+        format!("/home/me/.config/google-chrome/NativeMessagingHosts/{}.json", HOST_NAME),
+        format!("/home/me/.config/chromium/NativeMessagingHosts/{}.json", HOST_NAME),
+      ]
+    );
+    assert_eq!(manifests[0].1["allowed_extensions"], json!(["planner@example.org"]));
+    assert_eq!(manifests[1].1["allowed_origins"], json!(["chrome-extension://abcdefghijklmnop/"]));
+    assert_eq!(manifests[2].1["path"], "/usr/bin/daily-planner");
+  }
+}
@@ -0,0 +1,595 @@
+use crate::day::DATE_FORMAT;
+use crate::error::{AppError, AppResult};
+use chrono::{DateTime, Datelike, Duration, MappedLocalTime, Months, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Serialize, Deserialize, Type, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum Frequency {
+  Daily,
+  Weekly,
+  Monthly,
+  Yearly,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Type, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum RuleWeekday {
+  Monday,
+  Tuesday,
+  Wednesday,
+  Thursday,
+  Friday,
+  Saturday,
+  Sunday,
+}
+
+const WEEKDAY_CODES: [(&str, RuleWeekday); 7] = [
+  ("MO", RuleWeekday::Monday),
+  ("TU", RuleWeekday::Tuesday),
+  ("WE", RuleWeekday::Wednesday),
+  ("TH", RuleWeekday::Thursday),
+  ("FR", RuleWeekday::Friday),
+  ("SA", RuleWeekday::Saturday),
+  ("SU", RuleWeekday::Sunday),
+];
+
+impl RuleWeekday {
+  fn weekday(self) -> Weekday {
+    match self {
+      Self::Monday => Weekday::Mon,
+      Self::Tuesday => Weekday::Tue,
+      Self::Wednesday => Weekday::Wed,
+      Self::Thursday => Weekday::Thu,
+      Self::Friday => Weekday::Fri,
+      Self::Saturday => Weekday::Sat,
+      Self::Sunday => Weekday::Sun,
+    }
+  }
+}
+
+/// A `BYDAY` entry. With `nth` only that occurrence of the weekday in the month (in the year for yearly rules without `by_month`); negative counts from the end, so `-1` Friday is the last Friday.
+#[derive(Clone, Copy, Serialize, Deserialize, Type, Debug, PartialEq, Eq)]
+pub struct RuleDay {
+  pub weekday: RuleWeekday,
+  pub nth: Option<i32>,
+}
+
+fn default_interval() -> u32 {
+  1
+}
+
+/// An RFC 5545 `RRULE` with the `EXDATE`s of its series. Weeks start on Monday (`WKST=MO`).
+#[derive(Clone, Serialize, Deserialize, Type, Debug, PartialEq)]
+pub struct RecurrenceRule {
+  pub frequency: Frequency,
+  /// Every nth day, week, month or year; 0 is read as 1.
+  #[serde(default = "default_interval")]
+  pub interval: u32,
+  #[serde(default)]
+  pub by_day: Vec<RuleDay>,
+  /// Days of the month, negative from the end (`-1` is the last day). Months without the day are skipped, so `31` never falls in April.
+  #[serde(default)]
+  pub by_month_day: Vec<i32>,
+  /// Months 1 to 12.
+  #[serde(default)]
+  pub by_month: Vec<u32>,
+  pub count: Option<u32>,
+  /// Last possible start, inclusive: a date covers that whole day, a local date-time or RFC 3339 instant is exact.
+  pub until: Option<String>,
+  /// `EXDATE`s: a date removes that day's occurrences, a date-time only the one starting then. Removed occurrences still count towards `count`.
+  #[serde(default)]
+  pub exceptions: Vec<String>,
+  /// Only days the workday calendar marks as working. Unlike exceptions, skipped days do not count towards `count`.
+  #[serde(default)]
+  pub workdays_only: bool,
+}
+
+/// The frontend's holiday configuration (`holidays.ts`): holidays are off and `workdays` are weekend days that are worked instead.
+#[derive(Clone, Serialize, Deserialize, Type, Debug, Default, PartialEq)]
+pub struct WorkdayCalendar {
+  #[serde(default)]
+  pub holidays: Vec<String>,
+  #[serde(default)]
+  pub workdays: Vec<String>,
+}
+
+impl WorkdayCalendar {
+  pub fn is_workday(&self, date: NaiveDate) -> bool {
+    let key = date.format(DATE_FORMAT).to_string();
+    if self.workdays.contains(&key) {
+      return true;
+    }
+    !self.holidays.contains(&key) && !matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+  }
+}
+
+/// One occurrence for the calendar view.
+#[derive(Clone, Serialize, Deserialize, Type, Debug, PartialEq)]
+pub struct Occurrence {
+  /// RFC 3339 with the offset in effect that day, so occurrences keep their wall-clock time across DST changes.
+  pub start: String,
+  /// Local `YYYY-MM-DD`.
+  pub date: String,
+}
+
+fn invalid(message: String) -> AppError {
+  AppError::Serialization(message)
+}
+
+/// `YYYYMMDD`, `YYYYMMDDTHHMMSS` or `YYYYMMDDTHHMMSSZ` as used in RRULE text, in the formats `RecurrenceRule` stores.
+fn parse_ical_time(value: &str) -> AppResult<String> {
+  if let Ok(date) = NaiveDate::parse_from_str(value, "%Y%m%d") {
+    return Ok(date.format(DATE_FORMAT).to_string());
+  }
+  let (local, utc) = match value.strip_suffix('Z') {
+    Some(local) => (local, true),
+    None => (value, false),
+  };
+  let time = NaiveDateTime::parse_from_str(local, "%Y%m%dT%H%M%S").map_err(|_| invalid(format!("invalid UNTIL {}", value)))?;
+  Ok(format!("{}{}", time.format("%Y-%m-%dT%H:%M:%S"), if utc { "Z" } else { "" }))
+}
+
+fn parse_rule_day(value: &str) -> AppResult<RuleDay> {
+  let split = value.len().checked_sub(2).filter(|split| value.is_char_boundary(*split)).ok_or_else(|| invalid(format!("invalid BYDAY {}", value)))?;
+  let (nth, code) = value.split_at(split);
+  let weekday = WEEKDAY_CODES
+    .iter()
+    .find(|(name, _)| *name == code)
+    .map(|(_, weekday)| *weekday)
+    .ok_or_else(|| invalid(format!("invalid BYDAY {}", value)))?;
+  let nth = match nth {
+    "" => None,
+    nth => Some(nth.parse::<i32>().ok().filter(|nth| (1..=53).contains(&nth.abs())).ok_or_else(|| invalid(format!("invalid BYDAY {}", value)))?),
+  };
+  Ok(RuleDay { weekday, nth })
+}
+
+fn parse_list<T: FromStr>(name: &str, value: &str, valid: impl Fn(&T) -> bool) -> AppResult<Vec<T>> {
+  value
+    .split(',')
+    .map(|item| item.parse::<T>().ok().filter(&valid).ok_or_else(|| invalid(format!("invalid {} {}", name, item))))
+    .collect()
+}
+
+/// Parses RRULE text such as `FREQ=MONTHLY;BYDAY=-1FR;COUNT=6`, with or without the `RRULE:` prefix.
+impl FromStr for RecurrenceRule {
+  type Err = AppError;
+
+  fn from_str(text: &str) -> AppResult<Self> {
+    let text = text.trim();
+    let text = text.strip_prefix("RRULE:").unwrap_or(text);
+    let mut frequency = None;
+    let mut rule = RecurrenceRule {
+      frequency: Frequency::Daily,
+      interval: 1,
+      by_day: Vec::new(),
+      by_month_day: Vec::new(),
+      by_month: Vec::new(),
+      count: None,
+      until: None,
+      exceptions: Vec::new(),
+      workdays_only: false,
+    };
+
+    for part in text.split(';').filter(|part| !part.is_empty()) {
+      let (name, value) = part.split_once('=').ok_or_else(|| invalid(format!("invalid rule part {}", part)))?;
+      match name.to_ascii_uppercase().as_str() {
+        "FREQ" => {
+          frequency = Some(match value.to_ascii_uppercase().as_str() {
+            "DAILY" => Frequency::Daily,
+            "WEEKLY" => Frequency::Weekly,
+            "MONTHLY" => Frequency::Monthly,
+            "YEARLY" => Frequency::Yearly,
+            other => return Err(invalid(format!("unsupported FREQ {}", other))),
+          })
+        }
+        "INTERVAL" => rule.interval = value.parse::<u32>().ok().filter(|interval| *interval > 0).ok_or_else(|| invalid(format!("invalid INTERVAL {}", value)))?,
+        "COUNT" => rule.count = Some(value.parse::<u32>().map_err(|_| invalid(format!("invalid COUNT {}", value)))?),
+        "UNTIL" => rule.until = Some(parse_ical_time(value)?),
+        "BYDAY" => rule.by_day = value.split(',').map(|day| parse_rule_day(&day.to_ascii_uppercase())).collect::<AppResult<_>>()?,
+        "BYMONTHDAY" => rule.by_month_day = parse_list("BYMONTHDAY", value, |day: &i32| *day != 0 && day.abs() <= 31)?,
+        "BYMONTH" => rule.by_month = parse_list("BYMONTH", value, |month: &u32| (1..=12).contains(month))?,
+        "WKST" if value.eq_ignore_ascii_case("MO") => {}
+        other => return Err(invalid(format!("unsupported rule part {}", other))),
+      }
+    }
+
+    rule.frequency = frequency.ok_or_else(|| invalid("rule has no FREQ".to_string()))?;
+    if rule.count.is_some() && rule.until.is_some() {
+      return Err(invalid("COUNT and UNTIL cannot be combined".to_string()));
+    }
+    Ok(rule)
+  }
+}
+
+/// A date, local date-time or RFC 3339 instant as wall-clock time in `tz`; dates start at midnight.
+fn parse_local<Tz: TimeZone>(value: &str, tz: &Tz) -> Option<NaiveDateTime> {
+  if let Ok(instant) = DateTime::parse_from_rfc3339(value) {
+    return Some(instant.with_timezone(tz).naive_local());
+  }
+  ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%dT%H:%M"]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+    .or_else(|| NaiveDate::parse_from_str(value, DATE_FORMAT).ok().map(|date| date.and_time(NaiveTime::MIN)))
+}
+
+/// `until` and `exceptions` entries: a plain date matches the whole day.
+#[derive(Clone, Copy, Debug)]
+enum Bound {
+  Day(NaiveDate),
+  At(NaiveDateTime),
+}
+
+impl Bound {
+  fn parse<Tz: TimeZone>(value: &str, tz: &Tz) -> AppResult<Self> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, DATE_FORMAT) {
+      return Ok(Self::Day(date));
+    }
+    parse_local(value, tz).map(Self::At).ok_or_else(|| invalid(format!("invalid date {}", value)))
+  }
+
+  fn matches(self, time: NaiveDateTime) -> bool {
+    match self {
+      Self::Day(date) => time.date() == date,
+      Self::At(at) => time == at,
+    }
+  }
+
+  fn is_after(self, time: NaiveDateTime) -> bool {
+    match self {
+      Self::Day(date) => time.date() > date,
+      Self::At(at) => time > at,
+    }
+  }
+}
+
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+  NaiveDate::from_ymd_opt(year, month, 1)
+    .and_then(|first| first.checked_add_months(Months::new(1)))
+    .and_then(|next| next.pred_opt())
+    .map_or(28, |last| last.day())
+}
+
+/// Picks the `nth` entry of `dates` (1-based, negative from the end), or all of them.
+fn nth_of(dates: Vec<NaiveDate>, nth: Option<i32>) -> Vec<NaiveDate> {
+  let Some(nth) = nth else { return dates; };
+  let index = if nth > 0 { nth - 1 } else { dates.len() as i32 + nth };
+  usize::try_from(index).ok().and_then(|index| dates.get(index).copied()).into_iter().collect()
+}
+
+fn weekdays_between(first: NaiveDate, last: NaiveDate, weekday: Weekday) -> Vec<NaiveDate> {
+  first.iter_days().take_while(|date| *date <= last).filter(|date| date.weekday() == weekday).collect()
+}
+
+/// Candidate days of one month for monthly and yearly rules; `default_day` is the start's day when no `BY` part picks days.
+fn month_days(rule: &RecurrenceRule, year: i32, month: u32, default_day: u32) -> Vec<NaiveDate> {
+  let last = days_in_month(year, month) as i32;
+  let date = |day: i32| NaiveDate::from_ymd_opt(year, month, day as u32);
+
+  if !rule.by_month_day.is_empty() {
+    rule
+      .by_month_day
+      .iter()
+      .map(|day| if *day > 0 { *day } else { last + 1 + day })
+      .filter(|day| (1..=last).contains(day))
+      .filter_map(date)
+      .filter(|date| rule.by_day.is_empty() || rule.by_day.iter().any(|by| by.weekday.weekday() == date.weekday()))
+      .collect()
+  } else if !rule.by_day.is_empty() {
+    let (Some(first), Some(end)) = (date(1), date(last)) else { return Vec::new(); };
+    rule.by_day.iter().flat_map(|by| nth_of(weekdays_between(first, end, by.weekday.weekday()), by.nth)).collect()
+  } else {
+    date(default_day as i32).into_iter().collect()
+  }
+}
+
+/// Days of the `index`th period after the one holding `start`, or `None` once the calendar runs out.
+fn period(rule: &RecurrenceRule, start: NaiveDate, index: u32) -> Option<(NaiveDate, Vec<NaiveDate>)> {
+  let matches_filters = |date: &NaiveDate| {
+    (rule.by_month.is_empty() || rule.by_month.contains(&date.month()))
+      && (rule.by_month_day.is_empty() || month_days(rule, date.year(), date.month(), date.day()).contains(date))
+      && (rule.by_day.is_empty() || rule.by_day.iter().any(|by| by.weekday.weekday() == date.weekday()))
+  };
+
+  let (first, mut days) = match rule.frequency {
+    Frequency::Daily => {
+      let day = start.checked_add_signed(Duration::days(i64::from(index)))?;
+      (day, vec![day].into_iter().filter(matches_filters).collect::<Vec<_>>())
+    }
+    Frequency::Weekly => {
+      let monday = start.checked_sub_signed(Duration::days(i64::from(start.weekday().num_days_from_monday())))?;
+      let monday = monday.checked_add_signed(Duration::weeks(i64::from(index)))?;
+      let week = monday.iter_days().take(7).collect::<Vec<_>>();
+      let days = if rule.by_day.is_empty() {
+        week.into_iter().filter(|date| date.weekday() == start.weekday() && matches_filters(date)).collect()
+      } else {
+        week.into_iter().filter(matches_filters).collect()
+      };
+      (monday, days)
+    }
+    Frequency::Monthly => {
+      let first = start.with_day(1)?.checked_add_months(Months::new(index))?;
+      let days = if rule.by_month.is_empty() || rule.by_month.contains(&first.month()) { month_days(rule, first.year(), first.month(), start.day()) } else { Vec::new() };
+      (first, days)
+    }
+    Frequency::Yearly => {
+      let year = start.year().checked_add(i32::try_from(index).ok()?)?;
+      let first = NaiveDate::from_ymd_opt(year, 1, 1)?;
+      let days = if !rule.by_month.is_empty() {
+        rule.by_month.iter().flat_map(|month| month_days(rule, year, *month, start.day())).collect()
+      } else if !rule.by_month_day.is_empty() {
+        (1..=12).flat_map(|month| month_days(rule, year, month, start.day())).collect()
+      } else if !rule.by_day.is_empty() {
+        let last = NaiveDate::from_ymd_opt(year, 12, 31)?;
+        rule.by_day.iter().flat_map(|by| nth_of(weekdays_between(first, last, by.weekday.weekday()), by.nth)).collect()
+      } else {
+        // 29 February only recurs in leap years.
+        NaiveDate::from_ymd_opt(year, start.month(), start.day()).into_iter().collect()
+      };
+      (first, days)
+    }
+  };
+  days.sort();
+  days.dedup();
+  Some((first, days))
+}
+
+/// Local start times of the occurrences starting on `from` through `to`, following the rule from `start`.
+fn local_occurrences(rule: &RecurrenceRule, start: NaiveDateTime, from: NaiveDate, to: NaiveDate, calendar: &WorkdayCalendar, until: Option<Bound>, exceptions: &[Bound]) -> Vec<NaiveDateTime> {
+  let interval = rule.interval.max(1);
+  let mut generated = 0;
+  let mut found = Vec::new();
+
+  for index in (0..).step_by(interval as usize) {
+    let Some((first, days)) = period(rule, start.date(), index) else { break; };
+    if first > to || until.is_some_and(|until| until.is_after(first.and_time(NaiveTime::MIN))) {
+      break;
+    }
+    for time in days.into_iter().map(|date| date.and_time(start.time())).filter(|time| *time >= start) {
+      if time.date() > to || until.is_some_and(|until| until.is_after(time)) {
+        return found;
+      }
+      if rule.workdays_only && !calendar.is_workday(time.date()) {
+        continue;
+      }
+      generated += 1;
+      if rule.count.is_some_and(|count| generated > count) {
+        return found;
+      }
+      if time.date() >= from && !exceptions.iter().any(|exception| exception.matches(time)) {
+        found.push(time);
+      }
+    }
+  }
+  found
+}
+
+/// The instant a local time names in `tz`: the earlier one when clocks fall back, and for a time skipped when clocks
+/// spring forward the offset from before the change, which lands as far past the change as the time was into the gap (RFC 5545 §3.3.5).
+pub fn resolve<Tz: TimeZone>(tz: &Tz, time: NaiveDateTime) -> Option<DateTime<Tz>> {
+  match tz.from_local_datetime(&time) {
+    MappedLocalTime::Single(resolved) | MappedLocalTime::Ambiguous(resolved, _) => Some(resolved),
+    MappedLocalTime::None => {
+      let before = tz.offset_from_local_datetime(&(time - Duration::hours(3))).earliest()?.fix();
+      Some(tz.from_utc_datetime(&(time - Duration::seconds(i64::from(before.local_minus_utc())))))
+    }
+  }
+}
+
+/// Occurrences of `rule` for a series starting at `start` (a local date or date-time, or an RFC 3339 instant), limited to those
+/// starting on `from` through `to` in `tz`. The calendar view asks for its visible range in the system time zone.
+pub fn expand<Tz: TimeZone>(rule: &RecurrenceRule, start: &str, from: &str, to: &str, calendar: &WorkdayCalendar, tz: &Tz) -> AppResult<Vec<DateTime<Tz>>> {
+  let start = parse_local(start, tz).ok_or_else(|| invalid(format!("invalid start {}", start)))?;
+  let day = |value: &str| NaiveDate::parse_from_str(value, DATE_FORMAT).map_err(|_| invalid(format!("invalid date {}", value)));
+  let (from, to) = (day(from)?, day(to)?);
+  let until = rule.until.as_deref().map(|until| Bound::parse(until, tz)).transpose()?;
+  let exceptions = rule.exceptions.iter().map(|exception| Bound::parse(exception, tz)).collect::<AppResult<Vec<_>>>()?;
+
+  Ok(local_occurrences(rule, start, from, to, calendar, until, &exceptions).into_iter().filter_map(|time| resolve(tz, time)).collect())
+}
+
+/// Whether a series starting on `start` has an occurrence on `date`, for callers that only deal in days.
+pub fn occurs_on(rule: &RecurrenceRule, start: NaiveDate, date: NaiveDate, calendar: &WorkdayCalendar) -> bool {
+  let until = rule.until.as_deref().and_then(|until| Bound::parse(until, &Utc).ok());
+  let exceptions = rule.exceptions.iter().filter_map(|exception| Bound::parse(exception, &Utc).ok()).collect::<Vec<_>>();
+  !local_occurrences(rule, start.and_time(NaiveTime::MIN), date, date, calendar, until, &exceptions).is_empty()
+}
+
+pub fn to_occurrence<Tz: TimeZone>(time: &DateTime<Tz>) -> Occurrence
+where
+  Tz::Offset: std::fmt::Display,
+{
+  Occurrence { start: time.to_rfc3339(), date: time.naive_local().format(DATE_FORMAT).to_string() }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::{expand, Frequency, RecurrenceRule, RuleDay, RuleWeekday, WorkdayCalendar};
+  use chrono::{DateTime, FixedOffset, MappedLocalTime, NaiveDate, NaiveDateTime, TimeZone};
+
+  /// Central European time for 2026: summer time from 29 March 01:00 UTC to 25 October 01:00 UTC.
+  #[derive(Clone, Copy, Debug)]
+  pub struct Berlin;
+
+  impl Berlin {
+    fn offset_at_utc(utc: &NaiveDateTime) -> FixedOffset {
+      let spring = NaiveDate::from_ymd_opt(2026, 3, 29).unwrap().and_hms_opt(1, 0, 0).unwrap();
+      let autumn = NaiveDate::from_ymd_opt(2026, 10, 25).unwrap().and_hms_opt(1, 0, 0).unwrap();
+      FixedOffset::east_opt(if *utc >= spring && *utc < autumn { 7200 } else { 3600 }).unwrap()
+    }
+  }
+
+  impl TimeZone for Berlin {
+    type Offset = FixedOffset;
+
+    fn from_offset(_: &FixedOffset) -> Self {
+      Berlin
+    }
+
+    fn offset_from_local_date(&self, local: &NaiveDate) -> MappedLocalTime<FixedOffset> {
+      self.offset_from_local_datetime(&local.and_hms_opt(12, 0, 0).unwrap())
+    }
+
+    fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> MappedLocalTime<FixedOffset> {
+      let valid = [7200, 3600]
+        .iter()
+        .map(|seconds| FixedOffset::east_opt(*seconds).unwrap())
+        .filter(|offset| Self::offset_at_utc(&(*local - *offset)) == *offset)
+        .collect::<Vec<_>>();
+      match valid[..] {
+        [single] => MappedLocalTime::Single(single),
+        [earlier, later] => MappedLocalTime::Ambiguous(earlier, later),
+        _ => MappedLocalTime::None,
+      }
+    }
+
+    fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+      Self::offset_at_utc(&utc.and_hms_opt(0, 0, 0).unwrap())
+    }
+
+    fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+      Self::offset_at_utc(utc)
+    }
+  }
+
+  fn rule(text: &str) -> RecurrenceRule {
+    text.parse().unwrap()
+  }
+
+  fn starts(rule: &RecurrenceRule, start: &str, from: &str, to: &str) -> Vec<String> {
+    expand_with(rule, start, from, to, &WorkdayCalendar::default())
+  }
+
+  fn expand_with(rule: &RecurrenceRule, start: &str, from: &str, to: &str, calendar: &WorkdayCalendar) -> Vec<String> {
+    expand(rule, start, from, to, calendar, &Berlin).unwrap().iter().map(DateTime::to_rfc3339).collect()
+  }
+
+  fn dates(rule: &RecurrenceRule, start: &str, from: &str, to: &str) -> Vec<String> {
+    starts(rule, start, from, to).iter().map(|start| start[..10].to_string()).collect()
+  }
+
+  #[test]
+  fn parses_rrule_text() {
+    let parsed = rule("RRULE:FREQ=MONTHLY;INTERVAL=2;BYDAY=-1FR,+2mo;UNTIL=20261231T235959Z;WKST=MO");
+    assert_eq!(parsed.frequency, Frequency::Monthly);
+    assert_eq!(parsed.interval, 2);
+    assert_eq!(parsed.by_day, vec![RuleDay { weekday: RuleWeekday::Friday, nth: Some(-1) }, RuleDay { weekday: RuleWeekday::Monday, nth: Some(2) }]);
+    assert_eq!(parsed.until.as_deref(), Some("2026-12-31T23:59:59Z"));
+    assert_eq!(rule("FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=-1;COUNT=3").by_month_day, vec![-1]);
+
+    for invalid in ["INTERVAL=2", "FREQ=HOURLY", "FREQ=DAILY;COUNT=2;UNTIL=20260101", "FREQ=DAILY;BYMONTHDAY=0", "FREQ=WEEKLY;BYDAY=XX", "FREQ=WEEKLY;BYSETPOS=1", "FREQ=DAILY;INTERVAL=0"] {
+      assert!(invalid.parse::<RecurrenceRule>().is_err(), "{} should be rejected", invalid);
+    }
+  }
+
+  #[test]
+  fn intervals_count_from_the_start() {
+    assert_eq!(dates(&rule("FREQ=DAILY;INTERVAL=3"), "2026-03-01T09:00:00", "2026-03-05", "2026-03-12"), ["2026-03-07", "2026-03-10"]);
+    // Every other week on Monday and Thursday; the start's own week is the first.
+    assert_eq!(
+      dates(&rule("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH"), "2026-03-05T09:00:00", "2026-03-01", "2026-03-31"),
+      ["2026-03-05", "2026-03-16", "2026-03-19", "2026-03-30"]
+    );
+    assert_eq!(dates(&rule("FREQ=YEARLY;INTERVAL=2"), "2026-06-15", "2026-01-01", "2031-12-31"), ["2026-06-15", "2028-06-15", "2030-06-15"]);
+  }
+
+  #[test]
+  fn count_and_until_end_the_series() {
+    assert_eq!(dates(&rule("FREQ=WEEKLY;COUNT=3"), "2026-03-02T09:00:00", "2026-03-01", "2026-12-31"), ["2026-03-02", "2026-03-09", "2026-03-16"]);
+    // The count runs from the start even when the range begins later.
+    assert_eq!(dates(&rule("FREQ=DAILY;COUNT=5"), "2026-03-01T09:00:00", "2026-03-04", "2026-12-31"), ["2026-03-04", "2026-03-05"]);
+    // A date covers the whole day; a date-time excludes a later start that day.
+    assert_eq!(dates(&rule("FREQ=DAILY;UNTIL=20260303"), "2026-03-01T09:00:00", "2026-03-01", "2026-03-31"), ["2026-03-01", "2026-03-02", "2026-03-03"]);
+    assert_eq!(dates(&rule("FREQ=DAILY;UNTIL=20260303T080000"), "2026-03-01T09:00:00", "2026-03-01", "2026-03-31"), ["2026-03-01", "2026-03-02"]);
+  }
+
+  #[test]
+  fn exceptions_remove_occurrences_but_still_count() {
+    let mut weekly = rule("FREQ=WEEKLY;COUNT=4");
+    weekly.exceptions = vec!["2026-03-09".to_string(), "2026-03-16T10:00:00".to_string()];
+    // 16 March starts at 09:00, so the exception at 10:00 does not match it.
+    assert_eq!(dates(&weekly, "2026-03-02T09:00:00", "2026-03-01", "2026-12-31"), ["2026-03-02", "2026-03-16", "2026-03-23"]);
+  }
+
+  #[test]
+  fn month_end_days_are_skipped_or_counted_from_the_end() {
+    let from_the_31st = dates(&rule("FREQ=MONTHLY"), "2026-01-31", "2026-01-01", "2026-07-31");
+    assert_eq!(from_the_31st, ["2026-01-31", "2026-03-31", "2026-05-31", "2026-07-31"]);
+    let last_day = dates(&rule("FREQ=MONTHLY;BYMONTHDAY=-1"), "2026-01-31", "2026-01-01", "2026-04-30");
+    assert_eq!(last_day, ["2026-01-31", "2026-02-28", "2026-03-31", "2026-04-30"]);
+    assert_eq!(dates(&rule("FREQ=MONTHLY;BYMONTHDAY=30,-30"), "2026-01-01", "2026-02-01", "2026-03-31"), ["2026-03-02", "2026-03-30"]);
+    assert_eq!(dates(&rule("FREQ=YEARLY"), "2024-02-29", "2024-01-01", "2032-12-31"), ["2024-02-29", "2028-02-29", "2032-02-29"]);
+    assert_eq!(dates(&rule("FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=-1"), "2027-02-28", "2027-01-01", "2028-12-31"), ["2027-02-28", "2028-02-29"]);
+  }
+
+  #[test]
+  fn weekdays_can_be_picked_by_position() {
+    assert_eq!(
+      dates(&rule("FREQ=MONTHLY;BYDAY=-1FR"), "2026-01-01T16:00:00", "2026-01-01", "2026-05-31"),
+      ["2026-01-30", "2026-02-27", "2026-03-27", "2026-04-24", "2026-05-29"]
+    );
+    // Months with only four Thursdays have no fifth one.
+    assert_eq!(dates(&rule("FREQ=MONTHLY;BYDAY=5TH"), "2026-01-01", "2026-01-01", "2026-06-30"), ["2026-01-29", "2026-04-30"]);
+    // Friday the 13th: BYMONTHDAY narrowed by BYDAY.
+    assert_eq!(dates(&rule("FREQ=MONTHLY;BYMONTHDAY=13;BYDAY=FR"), "2026-01-01", "2026-01-01", "2026-12-31"), ["2026-02-13", "2026-03-13", "2026-11-13"]);
+    // The 20th Monday of the year, and Thanksgiving.
+    assert_eq!(dates(&rule("FREQ=YEARLY;BYDAY=20MO"), "2026-01-01", "2026-01-01", "2027-12-31"), ["2026-05-18", "2027-05-17"]);
+    assert_eq!(dates(&rule("FREQ=YEARLY;BYMONTH=11;BYDAY=4TH"), "2026-01-01", "2026-01-01", "2027-12-31"), ["2026-11-26", "2027-11-25"]);
+  }
+
+  #[test]
+  fn occurrences_before_the_start_are_not_generated() {
+    // The start's month has a last Friday before the start; it is not an occurrence.
+    assert_eq!(dates(&rule("FREQ=MONTHLY;BYDAY=-1FR;COUNT=2"), "2026-01-31", "2026-01-01", "2026-12-31"), ["2026-02-27", "2026-03-27"]);
+    // Same day but an earlier time than the start.
+    assert_eq!(starts(&rule("FREQ=WEEKLY;BYDAY=MO,TU;COUNT=1"), "2026-03-02T09:00:00", "2026-03-01", "2026-03-31"), ["2026-03-02T09:00:00+01:00"]);
+  }
+
+  #[test]
+  fn workdays_follow_the_holiday_calendar() {
+    let calendar = WorkdayCalendar { holidays: vec!["2026-02-17".to_string()], workdays: vec!["2026-02-15".to_string()] };
+    let mut daily = rule("FREQ=DAILY;COUNT=4");
+    daily.workdays_only = true;
+    // Sunday the 15th is worked, Tuesday the 17th is a holiday, and skipped days do not use up the count.
+    assert_eq!(
+      expand_with(&daily, "2026-02-14T09:00:00", "2026-02-01", "2026-02-28", &calendar).iter().map(|start| &start[..10]).collect::<Vec<_>>(),
+      ["2026-02-15", "2026-02-16", "2026-02-18", "2026-02-19"]
+    );
+    assert!(WorkdayCalendar::default().is_workday(NaiveDate::from_ymd_opt(2026, 2, 17).unwrap()));
+  }
+
+  #[test]
+  fn wall_clock_time_is_kept_across_dst_changes() {
+    let daily = rule("FREQ=DAILY");
+    assert_eq!(
+      starts(&daily, "2026-03-28T09:00:00", "2026-03-28", "2026-03-30"),
+      ["2026-03-28T09:00:00+01:00", "2026-03-29T09:00:00+02:00", "2026-03-30T09:00:00+02:00"]
+    );
+    // An RFC 3339 start is read as the local time it names.
+    assert_eq!(starts(&daily, "2026-10-24T07:00:00Z", "2026-10-25", "2026-10-25"), ["2026-10-25T09:00:00+01:00"]);
+  }
+
+  #[test]
+  fn skipped_and_repeated_local_times_resolve_like_rfc_5545() {
+    let daily = rule("FREQ=DAILY");
+    // 02:30 does not exist on 29 March; it is read with the winter offset, which is 03:30 summer time.
+    assert_eq!(
+      starts(&daily, "2026-03-28T02:30:00", "2026-03-28", "2026-03-30"),
+      ["2026-03-28T02:30:00+01:00", "2026-03-29T03:30:00+02:00", "2026-03-30T02:30:00+02:00"]
+    );
+    // 02:30 happens twice on 25 October; the first one counts.
+    assert_eq!(starts(&daily, "2026-10-24T02:30:00", "2026-10-25", "2026-10-25"), ["2026-10-25T02:30:00+02:00"]);
+  }
+
+  #[test]
+  fn ranges_and_inputs_are_checked() {
+    let daily = rule("FREQ=DAILY");
+    assert!(starts(&daily, "2026-03-10", "2026-03-01", "2026-03-09").is_empty());
+    assert!(starts(&daily, "2026-03-10", "2026-03-12", "2026-03-11").is_empty());
+    assert!(expand(&daily, "someday", "2026-03-01", "2026-03-09", &WorkdayCalendar::default(), &Berlin).is_err());
+    assert!(expand(&daily, "2026-03-10", "2026-03-01", "03/09/2026", &WorkdayCalendar::default(), &Berlin).is_err());
+  }
+}
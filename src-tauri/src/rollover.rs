@@ -0,0 +1,365 @@
+use crate::actions;
+use crate::day::{self, DATE_FORMAT};
+use crate::error::AppResult;
+use crate::events::{emit_day_rolled_over, report_error};
+use crate::recurrence::{days_in_month, occurs_on, resolve, Frequency, RecurrenceRule, WorkdayCalendar};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, SecondsFormat, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use specta::Type;
+use tauri::AppHandle;
+
+/// How far back a rollover fills in missed occurrences; after a longer break the older ones are skipped rather than
+/// filling the review with stale instances.
+const BACKFILL_DAYS: i64 = 31;
+
+fn parse_date(value: &str) -> Option<NaiveDate> {
+  NaiveDate::parse_from_str(value.get(..10)?, DATE_FORMAT).ok()
+}
+
+/// The day an ISO timestamp falls on in `tz`; plain dates and local date-times are taken as they are.
+fn local_date<Tz: TimeZone>(value: &str, tz: &Tz) -> Option<NaiveDate> {
+  match DateTime::parse_from_rfc3339(value) {
+    Ok(time) => Some(time.with_timezone(tz).date_naive()),
+    Err(_) => parse_date(value),
+  }
+}
+
+fn text<'a>(task: &'a Map<String, Value>, field: &str) -> Option<&'a str> {
+  task.get(field).and_then(Value::as_str)
+}
+
+/// The day the task is planned, scheduled or due; tasks with none of these have no day to roll over from.
+fn task_date<Tz: TimeZone>(task: &Map<String, Value>, tz: &Tz) -> Option<NaiveDate> {
+  ["plannedForDate", "scheduledStart", "dueAt"]
+    .iter()
+    .find_map(|field| text(task, field))
+    .and_then(|value| local_date(value, tz))
+}
+
+/// The task's `TaskRecurrence` as a rule for a series that starts on `anchor`. A monthly series on a month's
+/// last day keeps to last days; other days past a month's end skip that month.
+fn task_rule(recurrence: &Map<String, Value>, anchor: NaiveDate) -> Option<RecurrenceRule> {
+  let frequency = match text(recurrence, "frequency") {
+    Some("daily") => Frequency::Daily,
+    Some("weekly") => Frequency::Weekly,
+    Some("monthly") => Frequency::Monthly,
+    _ => return None,
+  };
+  let month_end = frequency == Frequency::Monthly && anchor.day() == days_in_month(anchor.year(), anchor.month());
+  Some(RecurrenceRule {
+    frequency,
+    interval: 1,
+    by_day: Vec::new(),
+    by_month_day: if month_end { vec![-1] } else { Vec::new() },
+    by_month: Vec::new(),
+    count: None,
+    until: text(recurrence, "endDate").map(str::to_string),
+    exceptions: Vec::new(),
+    workdays_only: recurrence.get("smartWorkdayOnly").and_then(Value::as_bool) == Some(true),
+  })
+}
+
+/// The days after the series' last instance and the previous rollover, through `today`, that get an instance. A store
+/// that was never rolled over starts with today.
+fn due_dates(recurrence: &Map<String, Value>, anchor: NaiveDate, from: Option<NaiveDate>, today: NaiveDate, calendar: &WorkdayCalendar) -> Vec<NaiveDate> {
+  let Some(rule) = task_rule(recurrence, anchor) else { return Vec::new(); };
+  let last_generated = text(recurrence, "lastGeneratedDate").and_then(parse_date);
+  let start = from.unwrap_or(today - Duration::days(1)).max(anchor).max(today - Duration::days(BACKFILL_DAYS));
+  let after = last_generated.map_or(start, |last| last.max(start));
+  after
+    .iter_days()
+    .skip(1)
+    .take_while(|date| *date <= today)
+    .filter(|date| occurs_on(&rule, anchor, *date, calendar))
+    .collect()
+}
+
+/// Moves an ISO timestamp or plain date by whole days, keeping its format. Timestamps keep their wall-clock time in `tz`
+/// across DST changes, like the calendar's occurrences.
+fn shift_days<Tz: TimeZone>(value: &str, days: i64, tz: &Tz) -> Option<String> {
+  if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+    let shifted = resolve(tz, time.with_timezone(tz).naive_local() + Duration::days(days))?;
+    return Some(shifted.with_timezone(&Utc).to_rfc3339_opts(SecondsFormat::Millis, true));
+  }
+  let date = parse_date(value)? + Duration::days(days);
+  Some(format!("{}{}", date.format(DATE_FORMAT), &value[10..]))
+}
+
+/// The series' instance on `date`. It carries no rule of its own and points back to the task that does with `seriesId`.
+fn next_instance<Tz: TimeZone>(task: &Map<String, Value>, anchor: NaiveDate, date: NaiveDate, tz: &Tz, now: &str) -> Map<String, Value> {
+  let mut next = task.clone();
+  let offset = (date - anchor).num_days();
+  for field in ["scheduledStart", "scheduledEnd", "dueAt"] {
+    if let Some(shifted) = text(task, field).and_then(|value| shift_days(value, offset, tz)) {
+      next.insert(field.to_string(), Value::String(shifted));
+    }
+  }
+  for field in ["completedAt", "reviewStatus", "recurrence"] {
+    next.remove(field);
+  }
+  next.insert("id".to_string(), Value::String(uuid::Uuid::new_v4().to_string()));
+  next.insert("seriesId".to_string(), task.get("id").cloned().unwrap_or(Value::Null));
+  next.insert("status".to_string(), "todo".into());
+  next.insert("planningState".to_string(), "today".into());
+  next.insert("plannedForDate".to_string(), date.format(DATE_FORMAT).to_string().into());
+  next.insert("isHighlight".to_string(), false.into());
+  next.insert("pomodoroSessions".to_string(), 0.into());
+  next.insert("pomodoroMinutes".to_string(), 0.into());
+  next.insert("createdAt".to_string(), now.into());
+  next.insert("updatedAt".to_string(), now.into());
+  next
+}
+
+/// What a rollover did, by task ID.
+#[derive(Clone, Serialize, Deserialize, Type, Debug, Default, PartialEq)]
+pub struct RolloverSummary {
+  /// Unfinished commitments from before the previous rollover, still waiting for review.
+  pub carried_forward: Vec<String>,
+  /// Unfinished commitments from the days that just ended, including the instances generated for them.
+  pub pending_review: Vec<String>,
+  /// New instances of recurring tasks: today's and those of the days that passed without a rollover.
+  pub generated: Vec<String>,
+}
+
+/// Rolls `tasks` (the persisted task objects, unknown fields kept) over from `from`, the day they were last rolled over
+/// to, to `today`. Only depends on those days, so running it twice changes nothing.
+fn roll_over<Tz: TimeZone>(tasks: &mut Vec<Map<String, Value>>, from: Option<NaiveDate>, today: NaiveDate, calendar: &WorkdayCalendar, tz: &Tz, now: &str) -> RolloverSummary {
+  let mut summary = RolloverSummary::default();
+  let mut instances = Vec::new();
+
+  for task in tasks.iter_mut() {
+    let Some(date) = task_date(task, tz) else { continue; };
+    if date >= today {
+      continue;
+    }
+
+    // Unfinished commitments wait in the Review workspace under the day they were planned for.
+    if text(task, "status") == Some("todo") && text(task, "planningState") == Some("today") {
+      let id = text(task, "id").unwrap_or_default().to_string();
+      if from.is_some_and(|from| date < from) {
+        summary.carried_forward.push(id);
+      } else {
+        summary.pending_review.push(id);
+      }
+      if text(task, "reviewStatus") != Some("pending") || task.get("plannedForDate").is_none() {
+        task.insert("reviewStatus".to_string(), "pending".into());
+        task.insert("plannedForDate".to_string(), date.format(DATE_FORMAT).to_string().into());
+        task.insert("updatedAt".to_string(), now.into());
+      }
+    }
+
+    // The rule stays on the series' first task, which the calendar expands too; `lastGeneratedDate` keeps a day from
+    // getting a second instance. Days missed while the app was closed get theirs too, already waiting for review.
+    let Some(recurrence) = task.get("recurrence").and_then(Value::as_object) else { continue; };
+    let dates = due_dates(recurrence, date, from, today, calendar);
+    let Some(last) = dates.last() else { continue; };
+    for day in &dates {
+      let mut instance = next_instance(task, date, *day, tz, now);
+      let id = text(&instance, "id").unwrap_or_default().to_string();
+      if *day < today {
+        instance.insert("reviewStatus".to_string(), "pending".into());
+        summary.pending_review.push(id.clone());
+      }
+      summary.generated.push(id);
+      instances.push(instance);
+    }
+    let last = last.format(DATE_FORMAT).to_string();
+    if let Some(recurrence) = task.get_mut("recurrence").and_then(Value::as_object_mut) {
+      recurrence.insert("lastGeneratedDate".to_string(), last.into());
+    }
+  }
+
+  tasks.extend(instances);
+  summary
+}
+
+/// The main window's tasks rolled over to a new planner day, for it to merge into its store.
+#[derive(Serialize, Deserialize, Type, Debug)]
+pub struct RolledOverTasks {
+  pub date: String,
+  /// The tasks that were sent, in order and with the rollover's changes, followed by the new instances.
+  pub tasks: Vec<Map<String, Value>>,
+  pub summary: RolloverSummary,
+}
+
+/// Rolls `tasks` (the store's task objects) over to the planner day, unless `last_date`, the day the store was last
+/// rolled over to, is already that day, and announces what changed with `day_rolled_over`. The main window merges and
+/// saves the result itself, together with the new `last_date`, so its autosave never writes over a rollover and a
+/// rollover is never recorded without its tasks.
+pub fn roll_over_tasks(handle: &AppHandle, mut tasks: Vec<Map<String, Value>>, last_date: Option<&str>, calendar: &WorkdayCalendar) -> AppResult<Option<RolledOverTasks>> {
+  let today = actions::pomodoro_state(handle).last_date;
+  if last_date.is_some_and(|last| !day::has_rolled_over(last, &today)) {
+    return Ok(None);
+  }
+  let Some(date) = parse_date(&today) else { return Ok(None); };
+
+  let now = Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true);
+  let summary = roll_over(&mut tasks, last_date.and_then(parse_date), date, calendar, &chrono::Local, &now);
+  report_error(handle, "day rollover", emit_day_rolled_over(handle, last_date.map(str::to_string), today.clone(), Some(summary.clone())));
+  Ok(Some(RolledOverTasks { date: today, tasks, summary }))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{roll_over, shift_days, RolloverSummary};
+  use crate::recurrence::tests::Berlin;
+  use crate::recurrence::WorkdayCalendar;
+  use chrono::{NaiveDate, Utc};
+  use serde_json::{json, Map, Value};
+
+  const NOW: &str = "2026-03-10T04:00:00.000Z";
+
+  fn date(value: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+  }
+
+  fn objects(tasks: Vec<Value>) -> Vec<Map<String, Value>> {
+    tasks.into_iter().map(|task| task.as_object().cloned().unwrap()).collect()
+  }
+
+  fn roll_with(tasks: &mut Vec<Map<String, Value>>, from: Option<&str>, today: &str, calendar: &WorkdayCalendar) -> RolloverSummary {
+    roll_over(tasks, from.map(date), date(today), calendar, &Utc, NOW)
+  }
+
+  fn roll(tasks: &mut Vec<Map<String, Value>>, from: &str, today: &str) -> RolloverSummary {
+    roll_with(tasks, Some(from), today, &WorkdayCalendar::default())
+  }
+
+  fn ids(tasks: &[Map<String, Value>]) -> Vec<String> {
+    tasks.iter().map(|task| task["id"].as_str().unwrap().to_string()).collect()
+  }
+
+  #[test]
+  fn unfinished_commitments_from_earlier_days_wait_for_review() {
+    let mut tasks = objects(vec![
+      json!({ "id": "a", "status": "todo", "planningState": "today", "plannedForDate": "2026-03-09", "reviewStatus": "carried_forward", "updatedAt": "2026-03-09T08:00:00.000Z", "custom": 1 }),
+      json!({ "id": "b", "status": "todo", "planningState": "today", "scheduledStart": "2026-03-07T08:00:00.000Z", "updatedAt": "2026-03-07T08:00:00.000Z" }),
+      json!({ "id": "c", "status": "done", "planningState": "today", "plannedForDate": "2026-03-09", "updatedAt": "2026-03-09T08:00:00.000Z" }),
+      json!({ "id": "d", "status": "todo", "planningState": "today", "plannedForDate": "2026-03-10", "updatedAt": "2026-03-10T01:00:00.000Z" }),
+      json!({ "id": "e", "status": "todo", "planningState": "today", "updatedAt": "2026-03-07T08:00:00.000Z" }),
+    ]);
+    let summary = roll(&mut tasks, "2026-03-09", "2026-03-10");
+
+    assert_eq!(summary, RolloverSummary { carried_forward: vec!["b".into()], pending_review: vec!["a".into()], generated: Vec::new() });
+    assert_eq!(tasks[0]["reviewStatus"], "pending");
+    assert_eq!(tasks[0]["custom"], 1);
+    // Pinned before `updatedAt` moves, so the task stays under the day it was scheduled for.
+    assert_eq!(tasks[1]["plannedForDate"], "2026-03-07");
+    assert!(tasks[2].get("reviewStatus").is_none());
+    assert!(tasks[3].get("reviewStatus").is_none());
+    // Without a planned, scheduled or due day there is nothing to roll over from.
+    assert!(tasks[4].get("reviewStatus").is_none());
+    assert!(tasks[4].get("plannedForDate").is_none());
+  }
+
+  #[test]
+  fn recurring_tasks_get_instances_from_the_task_with_the_rule() {
+    let mut tasks = objects(vec![json!({
+      "id": "standup", "status": "done", "planningState": "today", "plannedForDate": "2026-03-09",
+      "scheduledStart": "2026-03-09T01:30:00.000Z", "completedAt": "2026-03-09T02:00:00.000Z", "pomodoroSessions": 2,
+      "updatedAt": "2026-03-09T02:00:00.000Z", "recurrence": { "frequency": "daily", "smartWorkdayOnly": true },
+    })]);
+    let summary = roll(&mut tasks, "2026-03-09", "2026-03-10");
+
+    assert_eq!(summary.generated, ids(&tasks[1..]));
+    assert!(summary.pending_review.is_empty());
+    assert_eq!(tasks[0]["recurrence"]["frequency"], "daily");
+    assert_eq!(tasks[0]["recurrence"]["lastGeneratedDate"], "2026-03-10");
+    let next = &tasks[1];
+    assert_ne!(next["id"], "standup");
+    assert_eq!(next["seriesId"], "standup");
+    assert!(next.get("recurrence").is_none());
+    assert!(next.get("reviewStatus").is_none());
+    assert_eq!(next["status"], "todo");
+    assert_eq!(next["plannedForDate"], "2026-03-10");
+    assert_eq!(next["scheduledStart"], "2026-03-10T01:30:00.000Z");
+    assert_eq!(next["pomodoroSessions"], 0);
+    assert!(next.get("completedAt").is_none());
+
+    // A second run on the same day leaves the series alone; the next day continues it from the same task.
+    assert!(roll(&mut tasks, "2026-03-09", "2026-03-10").generated.is_empty());
+    assert_eq!(tasks.len(), 2);
+    assert_eq!(roll(&mut tasks, "2026-03-10", "2026-03-11").generated.len(), 1);
+    assert_eq!(tasks[2]["seriesId"], "standup");
+    assert_eq!(tasks[2]["scheduledStart"], "2026-03-11T01:30:00.000Z");
+  }
+
+  #[test]
+  fn series_wait_for_their_next_matching_day() {
+    let weekly = |end: Option<&str>| objects(vec![json!({
+      "id": "weekly", "status": "done", "plannedForDate": "2026-03-02", "updatedAt": "2026-03-02T08:00:00.000Z",
+      "recurrence": { "frequency": "weekly", "smartWorkdayOnly": false, "endDate": end },
+    })]);
+    let mut tasks = weekly(None);
+    assert!(roll(&mut tasks, "2026-03-05", "2026-03-06").generated.is_empty());
+    assert_eq!(roll(&mut tasks, "2026-03-06", "2026-03-09").generated.len(), 1);
+    assert!(roll(&mut weekly(Some("2026-03-08")), "2026-03-08", "2026-03-09").generated.is_empty());
+
+    let daily_workdays = |planned: &str| objects(vec![json!({
+      "id": "daily", "status": "todo", "planningState": "later", "plannedForDate": planned, "updatedAt": NOW,
+      "recurrence": { "frequency": "daily", "smartWorkdayOnly": true },
+    })]);
+    assert!(roll(&mut daily_workdays("2026-03-06"), "2026-03-06", "2026-03-07").generated.is_empty());
+
+    let mut monthly = objects(vec![json!({
+      "id": "rent", "status": "done", "plannedForDate": "2026-01-31", "updatedAt": NOW,
+      "recurrence": { "frequency": "monthly", "smartWorkdayOnly": false },
+    })]);
+    assert!(roll(&mut monthly, "2026-02-26", "2026-02-27").generated.is_empty());
+    assert_eq!(roll(&mut monthly, "2026-02-27", "2026-02-28").generated.len(), 1);
+    assert_eq!(monthly[0]["recurrence"]["lastGeneratedDate"], "2026-02-28");
+    assert_eq!(monthly[1]["plannedForDate"], "2026-02-28");
+    assert_eq!(roll(&mut monthly, "2026-02-28", "2026-03-31").generated.len(), 1);
+    assert_eq!(monthly[2]["plannedForDate"], "2026-03-31");
+  }
+
+  #[test]
+  fn workday_series_follow_the_holiday_calendar() {
+    let standup = || objects(vec![json!({
+      "id": "standup", "status": "done", "plannedForDate": "2026-03-09", "updatedAt": NOW,
+      "recurrence": { "frequency": "daily", "smartWorkdayOnly": true },
+    })]);
+    let calendar = WorkdayCalendar { holidays: vec!["2026-03-10".into()], workdays: vec!["2026-03-14".into()] };
+
+    assert!(roll_with(&mut standup(), Some("2026-03-09"), "2026-03-10", &calendar).generated.is_empty());
+    let mut tasks = standup();
+    tasks[0]["recurrence"]["lastGeneratedDate"] = json!("2026-03-13");
+    assert_eq!(roll_with(&mut tasks, Some("2026-03-13"), "2026-03-14", &calendar).generated.len(), 1);
+    assert_eq!(tasks[1]["plannedForDate"], "2026-03-14");
+  }
+
+  #[test]
+  fn days_missed_between_rollovers_get_instances_waiting_for_review() {
+    let series = || objects(vec![json!({
+      "id": "water", "status": "done", "planningState": "today", "plannedForDate": "2026-03-05", "updatedAt": NOW,
+      "recurrence": { "frequency": "daily", "smartWorkdayOnly": false },
+    })]);
+    let mut tasks = series();
+    let summary = roll(&mut tasks, "2026-03-05", "2026-03-09");
+
+    assert_eq!(summary.generated, ids(&tasks[1..]));
+    assert_eq!(summary.pending_review, ids(&tasks[1..4]));
+    let planned = tasks[1..].iter().map(|task| task["plannedForDate"].as_str().unwrap()).collect::<Vec<_>>();
+    assert_eq!(planned, ["2026-03-06", "2026-03-07", "2026-03-08", "2026-03-09"]);
+    assert!(tasks[1..4].iter().all(|task| task["reviewStatus"] == "pending"));
+    assert!(tasks[4].get("reviewStatus").is_none());
+    assert_eq!(tasks[0]["recurrence"]["lastGeneratedDate"], "2026-03-09");
+    assert!(roll(&mut tasks, "2026-03-05", "2026-03-09").generated.is_empty());
+
+    // A store rolled over for the first time does not know which days it missed, and one back from a long break
+    // only fills in the last few weeks.
+    assert_eq!(roll_with(&mut series(), None, "2026-03-09", &WorkdayCalendar::default()).generated.len(), 1);
+    assert_eq!(roll(&mut series(), "2026-01-05", "2026-03-09").generated.len(), 31);
+  }
+
+  #[test]
+  fn shifting_keeps_the_local_time_of_day() {
+    assert_eq!(shift_days("2026-03-09T18:00:00", 7, &Utc).as_deref(), Some("2026-03-16T18:00:00"));
+    assert_eq!(shift_days("2026-03-09T01:30:00.000Z", 1, &Utc).as_deref(), Some("2026-03-10T01:30:00.000Z"));
+    assert_eq!(shift_days("soon", 1, &Utc), None);
+    // 09:00 in Berlin is 08:00 UTC before summer time begins on 29 March and 07:00 UTC after.
+    assert_eq!(shift_days("2026-03-28T08:00:00.000Z", 1, &Berlin).as_deref(), Some("2026-03-29T07:00:00.000Z"));
+    assert_eq!(shift_days("2026-03-29T07:00:00.000Z", -1, &Berlin).as_deref(), Some("2026-03-28T08:00:00.000Z"));
+  }
+}
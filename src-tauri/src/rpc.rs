@@ -0,0 +1,372 @@
+use crate::actions;
+use crate::error::{AppError, AppResult};
+use crate::events::{report_error, TimerBus};
+use crate::{perform_toggle_timer, AppState};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Manager};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt};
+use tokio::sync::{broadcast, mpsc, Notify};
+use tokio::task::{AbortHandle, JoinSet};
+
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const APP_ERROR: i64 = -32000;
+const UNAUTHORIZED: i64 = -32001;
+/// Messages queued for one client. A subscriber that falls this far behind is disconnected rather than buffered without bound.
+const OUTGOING_CAPACITY: usize = 64;
+
+/// Handle of the running server, so toggling the setting can stop it again.
+pub struct RpcServer {
+  /// Where the TCP port and token are published for clients.
+  endpoint_path: PathBuf,
+  task: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl RpcServer {
+  pub fn new(endpoint_path: PathBuf) -> Self {
+    Self { endpoint_path, task: Mutex::new(None) }
+  }
+}
+
+#[derive(Deserialize)]
+struct RpcRequest {
+  #[serde(default)]
+  id: Value,
+  method: String,
+  #[serde(default)]
+  params: Value,
+}
+
+#[derive(Debug, PartialEq)]
+enum RpcCall {
+  Authenticate { token: String },
+  GetPomodoroState,
+  ToggleTimer,
+  SkipMode,
+  ResetTimer,
+  UpdateTaskName { name: Option<String> },
+  Subscribe,
+}
+
+#[derive(Debug, PartialEq)]
+struct RpcError {
+  code: i64,
+  message: String,
+  data: Option<Value>,
+}
+
+impl RpcError {
+  fn new(code: i64, message: impl Into<String>) -> Self {
+    Self { code, message: message.into(), data: None }
+  }
+}
+
+impl From<AppError> for RpcError {
+  fn from(err: AppError) -> Self {
+    Self { code: APP_ERROR, message: err.to_string(), data: serde_json::to_value(&err).ok() }
+  }
+}
+
+/// Method names mirror the Tauri commands so scripts and the frontend share one vocabulary.
+fn parse_call(method: &str, params: &Value) -> Result<RpcCall, RpcError> {
+  match method {
+    "authenticate" => params
+      .get("token")
+      .and_then(Value::as_str)
+      .map(|token| RpcCall::Authenticate { token: token.to_string() })
+      .ok_or_else(|| RpcError::new(INVALID_PARAMS, "authenticate expects { \"token\": string }")),
+    "get_pomodoro_state" => Ok(RpcCall::GetPomodoroState),
+    "toggle_timer" => Ok(RpcCall::ToggleTimer),
+    "skip_mode" => Ok(RpcCall::SkipMode),
+    "reset_timer" => Ok(RpcCall::ResetTimer),
+    "update_task_name" => match params.get("name") {
+      None | Some(Value::Null) => Ok(RpcCall::UpdateTaskName { name: None }),
+      Some(Value::String(name)) => Ok(RpcCall::UpdateTaskName { name: Some(name.clone()) }),
+      Some(_) => Err(RpcError::new(INVALID_PARAMS, "update_task_name expects { \"name\": string | null }")),
+    },
+    "subscribe" => Ok(RpcCall::Subscribe),
+    _ => Err(RpcError::new(METHOD_NOT_FOUND, format!("unknown method: {}", method))),
+  }
+}
+
+fn response(id: &Value, result: Result<Value, RpcError>) -> Value {
+  match result {
+    Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+    Err(err) => {
+      let mut error = json!({ "code": err.code, "message": err.message });
+      if let Some(data) = err.data {
+        error["data"] = data;
+      }
+      json!({ "jsonrpc": "2.0", "id": id, "error": error })
+    }
+  }
+}
+
+/// Compares without returning early, so response timing does not reveal how much of a guessed token matched.
+fn tokens_match(expected: &str, given: &str) -> bool {
+  expected.len() == given.len() && expected.bytes().zip(given.bytes()).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+/// Per-connection state; `token` is `None` on the Unix socket, whose file permissions already restrict access.
+struct Session {
+  token: Option<String>,
+  authenticated: bool,
+}
+
+impl Session {
+  fn new(token: Option<String>) -> Self {
+    Self { authenticated: token.is_none(), token }
+  }
+
+  fn authorize(&mut self, call: &RpcCall) -> Result<Option<Value>, RpcError> {
+    if let RpcCall::Authenticate { token } = call {
+      self.authenticated = match &self.token {
+        Some(expected) => tokens_match(expected, token),
+        None => true,
+      };
+      return if self.authenticated { Ok(Some(json!(true))) } else { Err(RpcError::new(UNAUTHORIZED, "invalid token")) };
+    }
+    if self.authenticated { Ok(None) } else { Err(RpcError::new(UNAUTHORIZED, "call authenticate first")) }
+  }
+}
+
+fn execute(handle: &AppHandle, call: RpcCall) -> AppResult<Value> {
+  match call {
+    RpcCall::ToggleTimer => perform_toggle_timer(handle)?,
+    RpcCall::SkipMode => actions::skip(handle)?,
+    RpcCall::ResetTimer => actions::reset(handle)?,
+    RpcCall::UpdateTaskName { name } => actions::set_task_name(handle, name)?,
+    RpcCall::GetPomodoroState | RpcCall::Authenticate { .. } | RpcCall::Subscribe => {}
+  }
+  Ok(serde_json::to_value(actions::pomodoro_state(handle))?)
+}
+
+/// Queues a reply. A client that lets `OUTGOING_CAPACITY` messages pile up is not reading and gets disconnected.
+fn queue(out: &mpsc::Sender<Value>, message: Value, overflowed: &Notify) {
+  if let Err(mpsc::error::TrySendError::Full(_)) = out.try_send(message) {
+    overflowed.notify_one();
+  }
+}
+
+/// Queues every timer event for the client; notifies `overflowed` and stops once the client no longer reads them.
+async fn forward_timer_events(mut events: broadcast::Receiver<crate::events::TimerEvent>, out: mpsc::Sender<Value>, overflowed: Arc<Notify>) {
+  loop {
+    match events.recv().await {
+      Ok(event) => {
+        let notification = json!({ "jsonrpc": "2.0", "method": "timer_event", "params": event });
+        match out.try_send(notification) {
+          Ok(()) => {}
+          Err(mpsc::error::TrySendError::Full(_)) => {
+            overflowed.notify_one();
+            break;
+          }
+          Err(mpsc::error::TrySendError::Closed(_)) => break,
+        }
+      }
+      Err(broadcast::error::RecvError::Lagged(_)) => continue,
+      Err(broadcast::error::RecvError::Closed) => break,
+    }
+  }
+}
+
+async fn serve_connection<S: AsyncRead + AsyncWrite + Unpin + Send + 'static>(handle: AppHandle, stream: S, token: Option<String>) {
+  // The writer and the subscription live in `tasks`, so aborting the connection stops them too.
+  let mut tasks = JoinSet::new();
+  let (reader, mut writer) = tokio::io::split(stream);
+  let (out, mut outgoing) = mpsc::channel::<Value>(OUTGOING_CAPACITY);
+  tasks.spawn(async move {
+    while let Some(message) = outgoing.recv().await {
+      if writer.write_all(format!("{}\n", message).as_bytes()).await.is_err() {
+        break;
+      }
+    }
+  });
+
+  let mut session = Session::new(token);
+  let mut subscription: Option<AbortHandle> = None;
+  let overflowed = Arc::new(Notify::new());
+  let mut lines = tokio::io::BufReader::new(reader).lines();
+  let mut stalled = false;
+  loop {
+    let line = tokio::select! {
+      line = lines.next_line() => line,
+      _ = overflowed.notified() => {
+        stalled = true;
+        break;
+      }
+    };
+    let Ok(Some(line)) = line else { break; };
+    if line.trim().is_empty() {
+      continue;
+    }
+    let request = match serde_json::from_str::<RpcRequest>(&line) {
+      Ok(request) => request,
+      Err(err) => {
+        queue(&out, response(&Value::Null, Err(RpcError::new(PARSE_ERROR, err.to_string()))), &overflowed);
+        continue;
+      }
+    };
+
+    let result = parse_call(&request.method, &request.params).and_then(|call| match session.authorize(&call)? {
+      Some(reply) => Ok(reply),
+      None if call == RpcCall::Subscribe => {
+        if subscription.is_none() {
+          subscription = Some(tasks.spawn(forward_timer_events(handle.state::<TimerBus>().subscribe(), out.clone(), overflowed.clone())));
+        }
+        Ok(json!(true))
+      }
+      None => execute(&handle, call).map_err(RpcError::from),
+    });
+    // Requests without an id are notifications and get no reply.
+    if !request.id.is_null() {
+      queue(&out, response(&request.id, result), &overflowed);
+    }
+  }
+
+  if let Some(subscription) = subscription {
+    subscription.abort();
+  }
+  drop(out);
+  if stalled {
+    // The writer may be blocked on a socket nobody reads; flushing would never finish.
+    tasks.abort_all();
+  }
+  // Lets the writer flush the last replies.
+  while tasks.join_next().await.is_some() {}
+}
+
+#[cfg(target_os = "linux")]
+pub fn socket_path() -> PathBuf {
+  crate::control::socket_path().with_file_name(format!("daily-planner-rpc-{}.sock", std::env::var("USER").unwrap_or_else(|_| "user".to_string())))
+}
+
+#[cfg(target_os = "linux")]
+async fn listen(handle: AppHandle, _endpoint_path: PathBuf, _port: u16) -> io::Result<()> {
+  let listener = crate::control::bind_private(&socket_path())?;
+  let mut connections = JoinSet::new();
+  loop {
+    let (stream, _) = listener.accept().await?;
+    while connections.try_join_next().is_some() {}
+    connections.spawn(serve_connection(handle.clone(), stream, None));
+  }
+}
+
+#[cfg(not(target_os = "linux"))]
+async fn listen(handle: AppHandle, endpoint_path: PathBuf, port: u16) -> io::Result<()> {
+  let listener = tokio::net::TcpListener::bind(("127.0.0.1", port)).await?;
+  let token = format!("{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple());
+  let endpoint = json!({ "host": "127.0.0.1", "port": listener.local_addr()?.port(), "token": token });
+  fs::write(&endpoint_path, serde_json::to_string_pretty(&endpoint)?)?;
+  #[cfg(unix)]
+  {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(&endpoint_path, fs::Permissions::from_mode(0o600))?;
+  }
+  let mut connections = JoinSet::new();
+  loop {
+    let (stream, _) = listener.accept().await?;
+    while connections.try_join_next().is_some() {}
+    connections.spawn(serve_connection(handle.clone(), stream, Some(token.clone())));
+  }
+}
+
+/// Starts or stops the server to match the `rpc` desktop setting; a running server is restarted to pick up port changes.
+/// Aborting the accept loop drops its `JoinSet` of connections, which disconnects every client.
+pub fn sync(handle: &AppHandle) {
+  let state = handle.state::<AppState>();
+  let settings = state.desktop_settings.lock().unwrap().rpc.clone();
+  let server = handle.state::<RpcServer>();
+  let mut running = server.task.lock().unwrap();
+
+  if let Some(task) = running.take() {
+    task.abort();
+    #[cfg(target_os = "linux")]
+    let _ = fs::remove_file(socket_path());
+    let _ = fs::remove_file(&server.endpoint_path);
+  }
+  if !settings.enabled {
+    return;
+  }
+
+  let endpoint_path = server.endpoint_path.clone();
+  let server_handle = handle.clone();
+  *running = Some(tauri::async_runtime::spawn(async move {
+    let result = listen(server_handle.clone(), endpoint_path, settings.port).await.map_err(AppError::from);
+    report_error(&server_handle, "JSON-RPC server", result);
+  }));
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{forward_timer_events, parse_call, response, tokens_match, RpcCall, RpcError, Session, INVALID_PARAMS, METHOD_NOT_FOUND, OUTGOING_CAPACITY, UNAUTHORIZED};
+  use crate::events::TimerEvent;
+  use serde_json::{json, Value};
+  use std::sync::Arc;
+  use tokio::sync::{broadcast, mpsc, Notify};
+
+  #[test]
+  fn methods_mirror_the_tauri_commands() {
+    assert_eq!(parse_call("toggle_timer", &Value::Null), Ok(RpcCall::ToggleTimer));
+    assert_eq!(
+      parse_call("update_task_name", &json!({ "name": "Write report" })),
+      Ok(RpcCall::UpdateTaskName { name: Some("Write report".to_string()) })
+    );
+    assert_eq!(parse_call("update_task_name", &json!({ "name": null })), Ok(RpcCall::UpdateTaskName { name: None }));
+    assert_eq!(parse_call("update_task_name", &json!({ "name": 3 })).unwrap_err().code, INVALID_PARAMS);
+    assert_eq!(parse_call("quit", &Value::Null).unwrap_err().code, METHOD_NOT_FOUND);
+  }
+
+  #[test]
+  fn tcp_sessions_need_the_token_first() {
+    let mut session = Session::new(Some("secret".to_string()));
+    assert_eq!(session.authorize(&RpcCall::ToggleTimer).unwrap_err().code, UNAUTHORIZED);
+    assert_eq!(session.authorize(&RpcCall::Authenticate { token: "guess".to_string() }).unwrap_err().code, UNAUTHORIZED);
+    assert_eq!(session.authorize(&RpcCall::Authenticate { token: "secret".to_string() }), Ok(Some(json!(true))));
+    assert_eq!(session.authorize(&RpcCall::ToggleTimer), Ok(None));
+  }
+
+  #[test]
+  fn tokens_must_match_exactly() {
+    assert!(tokens_match("secret", "secret"));
+    assert!(!tokens_match("secret", "secreT"));
+    assert!(!tokens_match("secret", "secret2"));
+    assert!(!tokens_match("secret", ""));
+  }
+
+  #[test]
+  fn socket_sessions_are_trusted() {
+    let mut session = Session::new(None);
+    assert_eq!(session.authorize(&RpcCall::GetPomodoroState), Ok(None));
+  }
+
+  #[test]
+  fn subscribers_that_stop_reading_are_dropped() {
+    let runtime = tokio::runtime::Runtime::new().expect("runtime");
+    runtime.block_on(async {
+      let (bus, events) = broadcast::channel(OUTGOING_CAPACITY * 2);
+      // The receiving end is never read, like a client that stopped reading its socket.
+      let (out, _unread) = mpsc::channel(OUTGOING_CAPACITY);
+      let overflowed = Arc::new(Notify::new());
+      let forwarder = tokio::spawn(forward_timer_events(events, out, overflowed.clone()));
+      for _ in 0..=OUTGOING_CAPACITY {
+        bus.send(TimerEvent::BreakCompleted).expect("send");
+      }
+
+      tokio::time::timeout(std::time::Duration::from_secs(5), forwarder).await.expect("forwarder stops").unwrap();
+      tokio::time::timeout(std::time::Duration::from_secs(5), overflowed.notified()).await.expect("connection is told to close");
+    });
+  }
+
+  #[test]
+  fn errors_follow_json_rpc_shape() {
+    let reply = response(&json!(7), Err(RpcError::new(METHOD_NOT_FOUND, "unknown method: quit")));
+    assert_eq!(reply, json!({ "jsonrpc": "2.0", "id": 7, "error": { "code": -32601, "message": "unknown method: quit" } }));
+  }
+}
@@ -0,0 +1,139 @@
+use crate::actions;
+use crate::desktop_settings::ShortcutSettings;
+use crate::error::{AppError, AppResult};
+use crate::events::report_error;
+use crate::{perform_open_floating, perform_toggle_timer, AppState};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutEvent, ShortcutState};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShortcutAction {
+  ToggleTimer,
+  Skip,
+  Reset,
+  ToggleFloating,
+  QuickCapture,
+}
+
+impl ShortcutAction {
+  /// The settings field the shortcut comes from, used in error messages.
+  fn setting_name(self) -> &'static str {
+    match self {
+      Self::ToggleTimer => "toggle_timer",
+      Self::Skip => "skip",
+      Self::Reset => "reset",
+      Self::ToggleFloating => "toggle_floating",
+      Self::QuickCapture => "quick_capture",
+    }
+  }
+}
+
+struct Binding<'a> {
+  action: ShortcutAction,
+  accelerator: &'a str,
+  shortcut: Shortcut,
+}
+
+/// Parses every assigned shortcut, rejecting invalid ones and duplicates before anything is registered.
+fn parse_bindings(settings: &ShortcutSettings) -> AppResult<Vec<Binding<'_>>> {
+  let assigned = [
+    (ShortcutAction::ToggleTimer, &settings.toggle_timer),
+    (ShortcutAction::Skip, &settings.skip),
+    (ShortcutAction::Reset, &settings.reset),
+    (ShortcutAction::ToggleFloating, &settings.toggle_floating),
+    (ShortcutAction::QuickCapture, &settings.quick_capture),
+  ];
+  let mut bindings: Vec<Binding> = Vec::new();
+  for (action, accelerator) in assigned {
+    let accelerator = accelerator.trim();
+    if accelerator.is_empty() {
+      continue;
+    }
+    let shortcut = accelerator
+      .parse::<Shortcut>()
+      .map_err(|err| AppError::Shortcut(format!("{} is not a valid shortcut for {}: {}", accelerator, action.setting_name(), err)))?;
+    if let Some(other) = bindings.iter().find(|binding| binding.shortcut == shortcut) {
+      return Err(AppError::Shortcut(format!(
+        "{} is assigned to both {} and {}",
+        accelerator,
+        other.action.setting_name(),
+        action.setting_name()
+      )));
+    }
+    bindings.push(Binding { action, accelerator, shortcut });
+  }
+  Ok(bindings)
+}
+
+/// Replaces the registered shortcuts; the ones that can be registered stay active even when others conflict.
+pub fn apply(handle: &AppHandle, settings: &ShortcutSettings) -> AppResult<()> {
+  let global = handle.global_shortcut();
+  global.unregister_all()?;
+  let conflicts = parse_bindings(settings)?
+    .into_iter()
+    .filter_map(|binding| {
+      global.register(binding.shortcut).err().map(|err| format!("{} for {} ({})", binding.accelerator, binding.action.setting_name(), err))
+    })
+    .collect::<Vec<_>>();
+  if conflicts.is_empty() {
+    Ok(())
+  } else {
+    Err(AppError::Shortcut(format!("already in use or not available: {}", conflicts.join(", "))))
+  }
+}
+
+fn toggle_floating(handle: &AppHandle) -> AppResult<()> {
+  #[cfg(not(target_os = "macos"))]
+  if let Some(window) = handle.get_webview_window("floating") {
+    if window.is_visible()? {
+      window.hide()?;
+      return Ok(());
+    }
+  }
+  perform_open_floating(handle)
+}
+
+fn perform(handle: &AppHandle, action: ShortcutAction) -> AppResult<()> {
+  match action {
+    ShortcutAction::ToggleTimer => perform_toggle_timer(handle),
+    ShortcutAction::Skip => actions::skip(handle),
+    ShortcutAction::Reset => actions::reset(handle),
+    ShortcutAction::ToggleFloating => toggle_floating(handle),
+    ShortcutAction::QuickCapture => actions::open_capture_window(handle),
+  }
+}
+
+/// Handler for the global-shortcut plugin; fires on key press only.
+pub fn on_shortcut(handle: &AppHandle, shortcut: &Shortcut, event: ShortcutEvent) {
+  if event.state() != ShortcutState::Pressed {
+    return;
+  }
+  let settings = handle.state::<AppState>().desktop_settings.lock().unwrap().shortcuts.clone();
+  let action = parse_bindings(&settings).ok().and_then(|bindings| bindings.into_iter().find(|binding| binding.shortcut == *shortcut).map(|binding| binding.action));
+  if let Some(action) = action {
+    report_error(handle, "global shortcut", perform(handle, action));
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{parse_bindings, ShortcutAction};
+  use crate::desktop_settings::ShortcutSettings;
+  use crate::error::AppError;
+
+  #[test]
+  fn unassigned_actions_are_skipped() {
+    let settings = ShortcutSettings { toggle_timer: "CommandOrControl+Shift+P".to_string(), quick_capture: " ".to_string(), ..ShortcutSettings::default() };
+    let bindings = parse_bindings(&settings).expect("valid shortcuts");
+    assert_eq!(bindings.iter().map(|binding| binding.action).collect::<Vec<_>>(), vec![ShortcutAction::ToggleTimer]);
+  }
+
+  #[test]
+  fn duplicates_and_typos_are_reported() {
+    let duplicate = ShortcutSettings { skip: "CmdOrCtrl+Shift+P".to_string(), reset: "CommandOrControl+Shift+P".to_string(), ..ShortcutSettings::default() };
+    assert!(matches!(parse_bindings(&duplicate), Err(AppError::Shortcut(message)) if message.contains("skip and reset")));
+
+    let typo = ShortcutSettings { toggle_floating: "Ctrl+Shfit+F".to_string(), ..ShortcutSettings::default() };
+    assert!(matches!(parse_bindings(&typo), Err(AppError::Shortcut(message)) if message.contains("toggle_floating")));
+  }
+}
@@ -0,0 +1,222 @@
+use crate::actions;
+use crate::desktop_settings::{StatusBarFormat, StatusBarSettings};
+use crate::error::{AppError, AppResult};
+use crate::events::{report_error, TimerBus, TimerEvent};
+use crate::{format_tray_text, format_tray_tooltip, AppState, PomodoroState};
+use serde_json::json;
+use std::fs;
+use std::io::{self, Write};
+use std::os::unix::fs::{FileTypeExt, OpenOptionsExt};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Manager};
+use tokio::sync::broadcast;
+
+/// How often a FIFO without a reader is retried, so a bar started while the timer is paused still gets a line.
+const FIFO_RETRY: Duration = Duration::from_secs(1);
+
+/// Handle of the running writer, so changing the setting can stop or restart it.
+#[derive(Default)]
+pub struct StatusBarWriter(Mutex<Option<JoinHandle<()>>>);
+
+fn default_path() -> PathBuf {
+  let user = std::env::var("USER").unwrap_or_else(|_| "user".to_string());
+  crate::control::socket_path().with_file_name(format!("daily-planner-{}.status", user))
+}
+
+fn output_path(settings: &StatusBarSettings) -> PathBuf {
+  if settings.path.trim().is_empty() { default_path() } else { PathBuf::from(settings.path.trim()) }
+}
+
+fn phase_seconds(state: &PomodoroState) -> u32 {
+  let minutes = match state.mode.as_str() {
+    "shortBreak" => state.settings.short_break_duration,
+    "longBreak" => state.settings.long_break_duration,
+    _ => state.settings.work_duration,
+  };
+  minutes.max(1) * 60
+}
+
+fn bar_text(state: &PomodoroState) -> String {
+  let text = format_tray_text(&state.mode, state.time_left, state.current_task.as_deref());
+  if state.is_active { text } else { format!("{} (暂停)", text) }
+}
+
+/// Renders one update. i3blocks gets `full_text`/`short_text` lines from a file, but only `full_text` in persist mode.
+pub fn render(format: StatusBarFormat, state: &PomodoroState, fifo: bool) -> String {
+  match format {
+    StatusBarFormat::Waybar => {
+      let total = phase_seconds(state);
+      let percentage = (total - state.time_left.min(total)) * 100 / total;
+      let mut class = vec![state.mode.as_str()];
+      if !state.is_active {
+        class.push("paused");
+      }
+      let output = json!({
+        "text": bar_text(state),
+        "tooltip": format_tray_tooltip(&state.mode, state.time_left, state.current_task.as_deref()),
+        "class": class,
+        "alt": state.mode,
+        "percentage": percentage,
+      });
+      format!("{}\n", output)
+    }
+    StatusBarFormat::Polybar => format!("{}\n", bar_text(state)),
+    StatusBarFormat::I3blocks if fifo => format!("{}\n", bar_text(state)),
+    StatusBarFormat::I3blocks => format!("{}\n{:02}:{:02}\n", bar_text(state), state.time_left / 60, state.time_left % 60),
+  }
+}
+
+enum Sink {
+  File(PathBuf),
+  /// The pipe stays open between updates; closing it would make `cat`-style readers see end of file.
+  Fifo { path: PathBuf, pipe: Option<fs::File> },
+}
+
+impl Sink {
+  fn open(settings: &StatusBarSettings) -> AppResult<Self> {
+    if settings.path.trim().is_empty() {
+      crate::control::prepare_runtime_dir()?;
+    }
+    let path = output_path(settings);
+    if !settings.fifo {
+      return Ok(Self::File(path));
+    }
+    match fs::metadata(&path) {
+      Ok(metadata) if metadata.file_type().is_fifo() => {}
+      Ok(_) => return Err(AppError::Io(format!("{} exists and is not a FIFO", path.display()))),
+      Err(_) => make_fifo(&path)?,
+    }
+    Ok(Self::Fifo { path, pipe: None })
+  }
+
+  fn write(&mut self, line: &str) -> io::Result<()> {
+    match self {
+      // Rename over the old file so bars polling it never read a half-written update.
+      Self::File(path) => {
+        let partial = path.with_extension("partial");
+        fs::write(&partial, line)?;
+        fs::rename(&partial, path)
+      }
+      Self::Fifo { path, pipe } => {
+        if pipe.is_none() {
+          // Non-blocking open fails with ENXIO while nobody reads; the update is dropped and retried later.
+          match fs::OpenOptions::new().write(true).custom_flags(libc::O_NONBLOCK).open(&*path) {
+            Ok(file) => *pipe = Some(file),
+            Err(err) if err.raw_os_error() == Some(libc::ENXIO) => return Ok(()),
+            Err(err) => return Err(err),
+          }
+        }
+        let result = pipe.as_mut().map_or(Ok(()), |file| file.write_all(line.as_bytes()));
+        if result.is_err() {
+          // The reader went away (or fell behind); reopen for the next one instead of failing.
+          *pipe = None;
+        }
+        Ok(())
+      }
+    }
+  }
+
+  fn is_waiting_for_reader(&self) -> bool {
+    matches!(self, Self::Fifo { pipe: None, .. })
+  }
+}
+
+fn make_fifo(path: &Path) -> AppResult<()> {
+  use std::os::unix::ffi::OsStrExt;
+
+  let c_path = std::ffi::CString::new(path.as_os_str().as_bytes()).map_err(|err| AppError::Io(err.to_string()))?;
+  if unsafe { libc::mkfifo(c_path.as_ptr(), 0o600) } != 0 {
+    return Err(io::Error::last_os_error().into());
+  }
+  Ok(())
+}
+
+async fn run(handle: AppHandle, settings: StatusBarSettings, mut events: broadcast::Receiver<TimerEvent>) {
+  let mut sink = match Sink::open(&settings) {
+    Ok(sink) => sink,
+    Err(err) => return report_error(&handle, "status bar output", Err(err)),
+  };
+  let mut line = render(settings.format, &actions::pomodoro_state(&handle), settings.fifo);
+  // Only the first failure of a streak is reported; the writer runs every second while the timer is active.
+  let mut failing = false;
+  let mut pending = true;
+
+  loop {
+    if pending {
+      let result = sink.write(&line);
+      if result.is_err() != failing {
+        failing = result.is_err();
+        report_error(&handle, "status bar output", result.map_err(AppError::from));
+      }
+      pending = sink.is_waiting_for_reader();
+    }
+
+    tokio::select! {
+      event = events.recv() => match event {
+        Ok(TimerEvent::Tick(state)) => {
+          line = render(settings.format, &state, settings.fifo);
+          pending = true;
+        }
+        Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+        Err(broadcast::error::RecvError::Closed) => break,
+      },
+      _ = tokio::time::sleep(FIFO_RETRY), if pending => {}
+    }
+  }
+}
+
+/// Starts, restarts or stops the writer to match the `status_bar` desktop setting.
+pub fn sync(handle: &AppHandle) {
+  let settings = handle.state::<AppState>().desktop_settings.lock().unwrap().status_bar.clone();
+  let writer = handle.state::<StatusBarWriter>();
+  let mut running = writer.0.lock().unwrap();
+
+  if let Some(task) = running.take() {
+    task.abort();
+  }
+  if !settings.enabled {
+    return;
+  }
+
+  let events = handle.state::<TimerBus>().subscribe();
+  *running = Some(tauri::async_runtime::spawn(run(handle.clone(), settings, events)));
+}
+
+#[cfg(test)]
+mod tests {
+  use super::render;
+  use crate::desktop_settings::StatusBarFormat;
+  use crate::{PomodoroSettings, PomodoroState};
+
+  fn state(mode: &str, time_left: u32, is_active: bool) -> PomodoroState {
+    PomodoroState {
+      time_left,
+      is_active,
+      mode: mode.to_string(),
+      sessions_completed: 0,
+      last_date: "2026-01-01".to_string(),
+      settings: PomodoroSettings::default(),
+      current_task: None,
+    }
+  }
+
+  #[test]
+  fn waybar_reports_progress_and_classes() {
+    let work = state("work", PomodoroSettings::default().work_duration * 60 / 4, false);
+    let output: serde_json::Value = serde_json::from_str(&render(StatusBarFormat::Waybar, &work, false)).expect("json line");
+    assert_eq!(output["percentage"], 75);
+    assert_eq!(output["class"], serde_json::json!(["work", "paused"]));
+    assert!(output["text"].as_str().unwrap().ends_with("(暂停)"));
+  }
+
+  #[test]
+  fn i3blocks_adds_short_text_only_for_files() {
+    let running = state("shortBreak", 4 * 60 + 5, true);
+    assert_eq!(render(StatusBarFormat::I3blocks, &running, false), "休息: 04:05\n04:05\n");
+    assert_eq!(render(StatusBarFormat::I3blocks, &running, true), "休息: 04:05\n");
+    assert_eq!(render(StatusBarFormat::Polybar, &running, true), "休息: 04:05\n");
+  }
+}
@@ -0,0 +1,311 @@
+use crate::actions;
+use crate::desktop_settings::{HookEvent, Webhook};
+use crate::error::{AppError, AppResult};
+use crate::events::{report_error, TimerBus, TimerEvent};
+use crate::hooks::{catch_up, transition};
+use crate::{AppState, PomodoroState};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+use tokio::sync::{broadcast, Notify};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// With the one-hour cap in `backoff_secs` this keeps retrying for about two days.
+const MAX_ATTEMPTS: u32 = 48;
+/// How long the worker sleeps when nothing is queued.
+const IDLE_SECS: u64 = 300;
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+struct OutboxItem {
+  url: String,
+  payload: Value,
+  attempts: u32,
+  /// Unix seconds before which the item is not retried.
+  next_attempt: u64,
+}
+
+/// Deliveries that have not succeeded yet, persisted so they survive offline periods and restarts.
+struct Outbox {
+  path: PathBuf,
+  items: Vec<OutboxItem>,
+}
+
+impl Outbox {
+  fn load(path: PathBuf) -> Self {
+    let items = fs::read_to_string(&path)
+      .ok()
+      .and_then(|content| serde_json::from_str::<Vec<OutboxItem>>(&content).ok())
+      .unwrap_or_default();
+    Self { path, items }
+  }
+
+  fn save(&self) -> AppResult<()> {
+    fs::write(&self.path, serde_json::to_string_pretty(&self.items)?)?;
+    Ok(())
+  }
+
+  fn next_due(&self) -> Option<u64> {
+    self.items.iter().map(|item| item.next_attempt).min()
+  }
+
+  fn first_due(&self, now: u64) -> Option<OutboxItem> {
+    self.items.iter().find(|item| item.next_attempt <= now).cloned()
+  }
+
+  /// Drops deliveries to URLs that are no longer configured, so a removed or edited webhook stops being retried.
+  fn prune(&mut self, webhooks: &[Webhook]) -> AppResult<()> {
+    let before = self.items.len();
+    self.items.retain(|item| webhooks.iter().any(|webhook| webhook.url.trim() == item.url));
+    if self.items.len() != before { self.save() } else { Ok(()) }
+  }
+}
+
+/// The worker's outbox, kept so changed settings can prune it.
+pub struct WebhookOutbox(Arc<Mutex<Outbox>>);
+
+fn now_secs() -> u64 {
+  SystemTime::now().duration_since(UNIX_EPOCH).map(|elapsed| elapsed.as_secs()).unwrap_or_default()
+}
+
+fn backoff_secs(attempts: u32) -> u64 {
+  (5u64 << attempts.min(10)).min(3600)
+}
+
+fn payload(event: HookEvent, state: &PomodoroState, occurred_at: &str) -> Value {
+  json!({
+    "event": event.as_str(),
+    "occurred_at": occurred_at,
+    "mode": state.mode,
+    "task": state.current_task,
+    "is_active": state.is_active,
+    "time_left": state.time_left,
+    "durations": {
+      "work": state.settings.work_duration,
+      "short_break": state.settings.short_break_duration,
+      "long_break": state.settings.long_break_duration,
+    },
+    "sessions_today": state.sessions_completed,
+  })
+}
+
+async fn deliver(client: &reqwest::Client, item: &OutboxItem) -> Result<(), String> {
+  let response = client.post(&item.url).timeout(REQUEST_TIMEOUT).json(&item.payload).send().await.map_err(|err| err.to_string())?;
+  if response.status().is_success() {
+    Ok(())
+  } else {
+    Err(format!("HTTP {}", response.status()))
+  }
+}
+
+/// Sends every item that is due, oldest first, and returns the failures, which are rescheduled with exponential backoff.
+/// The lock is released while a request is in flight, so events keep being queued; the item is found again by value.
+async fn deliver_due(client: &reqwest::Client, outbox: &Mutex<Outbox>, now: u64) -> AppResult<Vec<String>> {
+  let mut failures = Vec::new();
+  loop {
+    let due = outbox.lock().unwrap().first_due(now);
+    let Some(item) = due else { break; };
+    let result = deliver(client, &item).await;
+
+    let mut outbox = outbox.lock().unwrap();
+    let Some(index) = outbox.items.iter().position(|queued| *queued == item) else { continue; };
+    match result {
+      Ok(()) => {
+        outbox.items.remove(index);
+      }
+      Err(err) => {
+        let queued = &mut outbox.items[index];
+        queued.attempts += 1;
+        failures.push(format!("webhook {} failed (attempt {}): {}", queued.url, queued.attempts, err));
+        if queued.attempts >= MAX_ATTEMPTS {
+          outbox.items.remove(index);
+        } else {
+          queued.next_attempt = now + backoff_secs(queued.attempts);
+        }
+      }
+    }
+    outbox.save()?;
+  }
+  Ok(failures)
+}
+
+fn enqueue(handle: &AppHandle, outbox: &Mutex<Outbox>, event: HookEvent, state: &PomodoroState) -> AppResult<()> {
+  let webhooks = handle.state::<AppState>().desktop_settings.lock().unwrap().webhooks.clone();
+  let payload = payload(event, state, &chrono::Local::now().to_rfc3339());
+  let mut outbox = outbox.lock().unwrap();
+  let before = outbox.items.len();
+  outbox.items.extend(
+    webhooks
+      .into_iter()
+      .filter(|webhook| !webhook.url.trim().is_empty() && (webhook.events.is_empty() || webhook.events.contains(&event)))
+      .map(|webhook| OutboxItem { url: webhook.url.trim().to_string(), payload: payload.clone(), attempts: 0, next_attempt: 0 }),
+  );
+  if outbox.items.len() != before { outbox.save() } else { Ok(()) }
+}
+
+/// Queues a delivery for every transition. Runs apart from delivery, so a slow or unreachable endpoint never lets the
+/// timer's ticks overflow the channel.
+async fn record(handle: AppHandle, mut events: broadcast::Receiver<TimerEvent>, outbox: Arc<Mutex<Outbox>>, queued: Arc<Notify>) {
+  let mut last = actions::pomodoro_state(&handle);
+  loop {
+    let transitions = match events.recv().await {
+      Ok(event) => transition(&mut last, event).into_iter().collect(),
+      Err(broadcast::error::RecvError::Lagged(_)) => catch_up(&mut last, actions::pomodoro_state(&handle)),
+      Err(broadcast::error::RecvError::Closed) => break,
+    };
+    for (event, state) in transitions {
+      report_error(&handle, "webhook outbox", enqueue(&handle, &outbox, event, &state));
+      queued.notify_one();
+    }
+  }
+}
+
+async fn deliver_queued(handle: AppHandle, outbox: Arc<Mutex<Outbox>>, queued: Arc<Notify>) {
+  let client = reqwest::Client::new();
+  loop {
+    let now = now_secs();
+    match deliver_due(&client, &outbox, now).await {
+      Ok(failures) => {
+        for failure in failures {
+          report_error(&handle, "webhook delivery", Err(AppError::Io(failure)));
+        }
+      }
+      Err(err) => report_error(&handle, "webhook outbox", Err(err)),
+    }
+
+    let wait = outbox.lock().unwrap().next_due().map_or(IDLE_SECS, |due| due.saturating_sub(now).min(IDLE_SECS));
+    tokio::select! {
+      _ = queued.notified() => {}
+      _ = tokio::time::sleep(Duration::from_secs(wait)) => {}
+    }
+  }
+}
+
+/// Prunes the outbox to the configured webhooks; run at launch and when the webhook settings change.
+pub fn sync(handle: &AppHandle) {
+  let Some(outbox) = handle.try_state::<WebhookOutbox>() else { return; };
+  let webhooks = handle.state::<AppState>().desktop_settings.lock().unwrap().webhooks.clone();
+  let pruned = outbox.0.lock().unwrap().prune(&webhooks);
+  report_error(handle, "webhook outbox", pruned);
+}
+
+pub fn spawn_worker(handle: &AppHandle, outbox_path: PathBuf) {
+  let events = handle.state::<TimerBus>().subscribe();
+  let outbox = Arc::new(Mutex::new(Outbox::load(outbox_path)));
+  handle.manage(WebhookOutbox(outbox.clone()));
+  sync(handle);
+  let queued = Arc::new(Notify::new());
+  tauri::async_runtime::spawn(record(handle.clone(), events, outbox.clone(), queued.clone()));
+  tauri::async_runtime::spawn(deliver_queued(handle.clone(), outbox, queued));
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{backoff_secs, deliver_due, payload, Outbox, OutboxItem};
+  use crate::desktop_settings::{HookEvent, Webhook};
+  use crate::{PomodoroSettings, PomodoroState};
+  use std::sync::Mutex;
+  use tokio::io::{AsyncReadExt, AsyncWriteExt};
+  use tokio::net::TcpListener;
+
+  /// Accepts one request, answers it with `status` and returns the request body.
+  async fn mock_server(status: u16) -> (String, tokio::task::JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    let server = tokio::spawn(async move {
+      let (mut stream, _) = listener.accept().await.expect("accept");
+      let mut request = Vec::new();
+      let mut buffer = [0u8; 4096];
+      loop {
+        let read = stream.read(&mut buffer).await.expect("read");
+        request.extend_from_slice(&buffer[..read]);
+        let text = String::from_utf8_lossy(&request).to_string();
+        if let Some((head, body)) = text.split_once("\r\n\r\n") {
+          let length = head
+            .lines()
+            .find_map(|line| line.to_ascii_lowercase().strip_prefix("content-length:").map(|value| value.trim().parse::<usize>().unwrap()))
+            .unwrap_or(0);
+          if body.len() >= length || read == 0 {
+            stream.write_all(format!("HTTP/1.1 {} Mock\r\ncontent-length: 0\r\nconnection: close\r\n\r\n", status).as_bytes()).await.unwrap();
+            return body.to_string();
+          }
+        }
+        if read == 0 {
+          return String::new();
+        }
+      }
+    });
+    (url, server)
+  }
+
+  fn finished_work() -> PomodoroState {
+    PomodoroState {
+      time_left: 0,
+      is_active: false,
+      mode: "work".to_string(),
+      sessions_completed: 3,
+      last_date: "2026-01-01".to_string(),
+      settings: PomodoroSettings::default(),
+      current_task: Some("Write report".to_string()),
+    }
+  }
+
+  #[test]
+  fn backoff_doubles_up_to_an_hour() {
+    assert_eq!(backoff_secs(1), 10);
+    assert_eq!(backoff_secs(2), 20);
+    assert_eq!(backoff_secs(30), 3600);
+  }
+
+  #[test]
+  fn deliveries_to_removed_webhooks_are_dropped() {
+    let path = std::env::temp_dir().join(format!("daily-planner-outbox-prune-{}.json", std::process::id()));
+    let item = |url: &str| OutboxItem { url: url.to_string(), payload: serde_json::Value::Null, attempts: 3, next_attempt: 0 };
+    let mut outbox = Outbox { path: path.clone(), items: vec![item("https://kept.example/hook"), item("https://old.example/hook")] };
+
+    let webhooks = [Webhook { url: " https://kept.example/hook ".to_string(), events: Vec::new() }];
+    outbox.prune(&webhooks).expect("save outbox");
+    assert_eq!(outbox.items, vec![item("https://kept.example/hook")]);
+    assert_eq!(Outbox::load(path.clone()).items, outbox.items);
+
+    outbox.prune(&[]).expect("save outbox");
+    assert!(outbox.items.is_empty());
+    let _ = std::fs::remove_file(path);
+  }
+
+  #[test]
+  fn failed_deliveries_stay_in_the_outbox_until_they_succeed() {
+    let runtime = tokio::runtime::Runtime::new().expect("runtime");
+    runtime.block_on(async {
+      let path = std::env::temp_dir().join(format!("daily-planner-outbox-{}.json", std::process::id()));
+      let client = reqwest::Client::new();
+      let (url, received) = mock_server(503).await;
+      let item = OutboxItem { url, payload: payload(HookEvent::WorkFinished, &finished_work(), "2026-01-01T10:00:00+00:00"), attempts: 0, next_attempt: 0 };
+      let outbox = Mutex::new(Outbox { path: path.clone(), items: vec![item] });
+
+      assert_eq!(deliver_due(&client, &outbox, 100).await.expect("save outbox").len(), 1);
+      let body: serde_json::Value = serde_json::from_str(&received.await.unwrap()).expect("json body");
+      assert_eq!(body["event"], "workFinished");
+      assert_eq!(body["task"], "Write report");
+      assert_eq!(body["sessions_today"], 3);
+      assert_eq!(outbox.lock().unwrap().items[0].attempts, 1);
+      assert_eq!(outbox.lock().unwrap().items[0].next_attempt, 100 + backoff_secs(1));
+      assert_eq!(Outbox::load(path.clone()).items, outbox.lock().unwrap().items);
+
+      // Not due yet: nothing is sent.
+      assert!(deliver_due(&client, &outbox, 101).await.expect("save outbox").is_empty());
+      assert_eq!(outbox.lock().unwrap().items[0].attempts, 1);
+
+      let (url, received) = mock_server(204).await;
+      outbox.lock().unwrap().items[0].url = url;
+      assert!(deliver_due(&client, &outbox, 200).await.expect("save outbox").is_empty());
+      received.await.unwrap();
+      assert!(outbox.lock().unwrap().items.is_empty());
+      assert!(Outbox::load(path.clone()).items.is_empty());
+      let _ = std::fs::remove_file(path);
+    });
+  }
+}
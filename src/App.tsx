@@ -1,19 +1,23 @@
 import { Suspense, lazy, useEffect, useMemo, useRef, useState } from 'react';
 import { CircleHelp, ClipboardCheck, Inbox, Loader2, Settings, Target } from 'lucide-react';
 import { format, parseISO } from 'date-fns';
-import { invoke } from '@tauri-apps/api/core';
 import SettingsDialog from './components/Settings';
 import { Button } from './components/ui/button';
 import { getWorkflowCopy } from './content/workflowCopy';
 import { FeedbackProvider } from './contexts/FeedbackContext';
 import { useI18n } from './i18n';
 import { useAppStore } from './stores/useAppStore';
-import { getOngoingTask, getPlanningState, getTaskReviewDate } from './utils/taskActivity';
+import { createInboxTask, getOngoingTask, getPlanningState, getTaskReviewDate } from './utils/taskActivity';
 import { cn } from './utils/cn';
 import { isTauriRuntime } from './utils/runtime';
+import { usePlannerDate } from './utils/plannerDate';
+import { startRollover } from './utils/rollover';
+import { commands, events, type MainRequest } from './bindings';
+import { logCommandError, unwrapResult } from './utils/commandResult';
 import FloatingPomodoro from './views/FloatingPomodoro';
 import FloatingPomodoroSettings from './views/FloatingPomodoroSettings';
 import NotificationView from './views/NotificationView';
+import QuickCapture from './views/QuickCapture';
 import GettingStartedDialog from './components/GettingStartedDialog';
 
 const InboxWorkspace = lazy(() => import('./components/InboxWorkspace'));
@@ -22,7 +26,6 @@ const ReviewWorkspace = lazy(() => import('./components/ReviewWorkspace'));
 
 const LEGACY_IMPORT_MARKER = 'daily-planner-legacy-imported-daily-planner-ai-v1';
 const GUIDE_MARKER = 'daily-planner-guide-v2-seen';
-const todayDate = () => format(new Date(), 'yyyy-MM-dd');
 
 const App = () => {
   const { locale, t } = useI18n();
@@ -33,7 +36,7 @@ const App = () => {
   const weeklyReportsCount = useAppStore((state) => state.weeklyReports.length);
   const habitsCount = useAppStore((state) => state.habits.length);
   const chatHistoryCount = useAppStore((state) => state.chatHistory.length);
-  const today = todayDate();
+  const today = usePlannerDate();
   const tasks = useAppStore((state) => state.tasks);
   const currentTaskId = useAppStore((state) => state.currentTaskId);
   const workflowMetrics = useMemo(() => {
@@ -79,6 +82,12 @@ const App = () => {
   const setIsSettingsOpen = useAppStore((state) => state.setIsSettingsOpen);
   const importData = useAppStore((state) => state.importData);
   const [activeTab, setActiveTab] = useState<'inbox' | 'today' | 'review'>('today');
+  const [todayViewRequest, setTodayViewRequest] = useState<{ view: 'plan' | 'calendar' } | null>(null);
+  // A calendar request only applies to the navigation that made it; later tab switches open Today normally.
+  const openTab = (tab: 'inbox' | 'today' | 'review') => {
+    setTodayViewRequest(null);
+    setActiveTab(tab);
+  };
   const [isGuideOpen, setIsGuideOpen] = useState(false);
   const notifiedRef = useRef<Set<string>>(new Set());
   const copy = getWorkflowCopy(locale);
@@ -86,7 +95,7 @@ const App = () => {
   const view = useMemo(() => {
     const params = new URLSearchParams(window.location.search);
     const candidate = params.get('view');
-    return candidate === 'floating' || candidate === 'floating-settings' || candidate === 'notification' ? candidate : 'main';
+    return candidate === 'floating' || candidate === 'floating-settings' || candidate === 'notification' || candidate === 'capture' ? candidate : 'main';
   }, []);
 
   const navItems = useMemo(() => ([
@@ -106,7 +115,7 @@ const App = () => {
 
     const notify = (title: string, body: string) => {
       if (!isTauriRuntime()) return;
-      invoke('show_notification', { title, body }).catch(() => undefined);
+      commands.showNotification(title, body).then(unwrapResult).catch(logCommandError);
     };
 
     const run = () => {
@@ -161,7 +170,8 @@ const App = () => {
     }
 
     let cancelled = false;
-    invoke<string | null>('load_legacy_daily_planner_ai_store')
+    commands.loadLegacyDailyPlannerAiStore()
+      .then(unwrapResult)
       .then((payload) => {
         if (cancelled || !payload) return;
         importData(JSON.parse(payload) as Record<string, unknown>);
@@ -197,6 +207,46 @@ const App = () => {
     return () => window.clearInterval(timer);
   }, [_hasHydrated, setCurrentTaskId, view]);
 
+  useEffect(() => {
+    if (view !== 'main' || !_hasHydrated || !isTauriRuntime()) return undefined;
+
+    const applyRequest = (request: MainRequest) => {
+      const state = useAppStore.getState();
+      if (request.kind === 'navigate') {
+        setTodayViewRequest(request.view === 'calendar' ? { view: 'calendar' } : null);
+        setActiveTab(request.view === 'calendar' ? 'today' : request.view);
+      } else if (request.kind === 'capture') {
+        state.addTask(createInboxTask(request.text));
+      } else if (request.kind === 'importNote') {
+        state.addTask({ ...createInboxTask(request.title), notes: request.notes ?? undefined });
+      } else {
+        const title = request.title.trim().toLowerCase();
+        const existing = state.tasks.find((task) => task.status === 'todo' && task.title.trim().toLowerCase() === title);
+        const task = existing || createInboxTask(request.title);
+        if (!existing) state.addTask(task);
+        setCurrentTaskId(task.id);
+      }
+    };
+
+    const drainRequests = () => {
+      commands.takeMainRequests()
+        .then(unwrapResult)
+        .then((requests) => requests.forEach(applyRequest))
+        .catch(logCommandError);
+    };
+
+    drainRequests();
+    const unlisten = events.mainRequestsQueued.listen(drainRequests);
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [_hasHydrated, setCurrentTaskId, view]);
+
+  useEffect(() => {
+    if (view !== 'main' || !_hasHydrated) return undefined;
+    return startRollover();
+  }, [_hasHydrated, view]);
+
   useEffect(() => {
     if (view !== 'main' || !_hasHydrated || typeof localStorage === 'undefined') return;
     if (localStorage.getItem(GUIDE_MARKER)) return;
@@ -206,6 +256,7 @@ const App = () => {
   if (view === 'floating') return <FloatingPomodoro />;
   if (view === 'floating-settings') return <FloatingPomodoroSettings />;
   if (view === 'notification') return <NotificationView />;
+  if (view === 'capture') return <QuickCapture />;
 
   if (!_hasHydrated) {
     return (
@@ -220,7 +271,7 @@ const App = () => {
 
   const content = {
     inbox: <InboxWorkspace />,
-    today: <TodayWorkspace />,
+    today: <TodayWorkspace viewRequest={todayViewRequest} />,
     review: <ReviewWorkspace />,
   }[activeTab];
 
@@ -251,7 +302,7 @@ const App = () => {
             <button
               key={item.id}
               type="button"
-              onClick={() => setActiveTab(item.id as typeof activeTab)}
+              onClick={() => openTab(item.id as typeof activeTab)}
               data-testid={`nav-${item.id}`}
               className={cn(
                 'flex w-full items-center gap-3 rounded-2xl px-4 py-3 text-left transition',
@@ -318,7 +369,7 @@ const App = () => {
             if (typeof localStorage !== 'undefined') localStorage.setItem(GUIDE_MARKER, '1');
           }}
           onJump={(tab) => {
-            setActiveTab(tab);
+            openTab(tab);
             setIsGuideOpen(false);
             if (typeof localStorage !== 'undefined') localStorage.setItem(GUIDE_MARKER, '1');
           }}
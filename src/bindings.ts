@@ -0,0 +1,566 @@
+// @ts-nocheck
+/* eslint-disable */
+// This file was generated by [tauri-specta](https://github.com/oscartbeaumont/tauri-specta). Do not edit this file manually.
+
+/** user-defined commands **/
+
+
+export const commands = {
+async getPomodoroState() : Promise<Result<PomodoroState, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_pomodoro_state") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async toggleTimer() : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("toggle_timer") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async resetTimer() : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("reset_timer") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async skipMode() : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("skip_mode") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateSettings(settings: PomodoroSettings) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_settings", { settings }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async openMain(view: MainView | null) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("open_main", { view }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async showNotification(title: string, body: string) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("show_notification", { title, body }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async toggleFloatingWindow(mode: FloatingMode | null, width: number | null, height: number | null) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("toggle_floating_window", { mode, width, height }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async openFloatingMode(mode: FloatingMode, width: number | null, height: number | null) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("open_floating_mode", { mode, width, height }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async openFloatingSettings() : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("open_floating_settings") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getRuntimePlatform() : Promise<Result<string, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_runtime_platform") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async broadcastFloatingPreferences(theme: string, opacity: number) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("broadcast_floating_preferences", { theme, opacity }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateTaskName(name: string | null) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_task_name", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setFloatingClickThrough(enabled: boolean) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_floating_click_through", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getDesktopSettings() : Promise<Result<DesktopSettings, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_desktop_settings") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateDesktopSettings(settings: DesktopSettings) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_desktop_settings", { settings }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setMqttPassword(password: string) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_mqtt_password", { password }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async openCaptureWindow() : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("open_capture_window") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async submitCapture(text: string) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("submit_capture", { text }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async takeMainRequests() : Promise<Result<MainRequest[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("take_main_requests") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async rollOverTasks(tasks: Partial<{ [key in string]: JsonValue }>[], lastDate: string | null, calendar: WorkdayCalendar) : Promise<Result<RolledOverTasks | null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("roll_over_tasks", { tasks, lastDate, calendar }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async expandRecurrence(rule: RecurrenceRule, start: string, from: string, to: string, calendar: WorkdayCalendar) : Promise<Result<Occurrence[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("expand_recurrence", { rule, start, from, to, calendar }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async parseRecurrenceRule(rule: string) : Promise<Result<RecurrenceRule, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("parse_recurrence_rule", { rule }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async loadLegacyDailyPlannerAiStore() : Promise<Result<string | null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("load_legacy_daily_planner_ai_store") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async loadLegacyNativeStoreValue(name: string) : Promise<Result<string | null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("load_legacy_native_store_value", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+}
+}
+
+/** user-defined events **/
+
+
+export const events = __makeEvents__<{
+backendError: BackendError,
+dayRolledOver: DayRolledOver,
+floatingEvent: FloatingEvent,
+mainRequestsQueued: MainRequestsQueued,
+settingsEvent: SettingsEvent,
+timerEvent: TimerEvent
+}>({
+backendError: "backend_error",
+dayRolledOver: "day_rolled_over",
+floatingEvent: "floating-event",
+mainRequestsQueued: "main-requests-queued",
+settingsEvent: "settings-event",
+timerEvent: "timer-event"
+})
+
+/** user-defined constants **/
+
+
+
+/** user-defined types **/
+
+/**
+ * Error returned by every command and carried by `backend_error`; serialized as `{ kind, message }` for the frontend.
+ */
+export type AppError = { kind: "window"; message: string } | { kind: "io"; message: string } | { kind: "serialization"; message: string } | { kind: "notification"; message: string } | { kind: "emit"; message: string } | 
+/**
+ * A global shortcut that is invalid, assigned twice or already taken by another application.
+ */
+{ kind: "shortcut"; message: string }
+/**
+ * Launch at login, with the main window hidden so only the tray, timer and reminders start.
+ */
+export type AutostartSettings = { enabled: boolean }
+/**
+ * A failure outside any command call (timer loop, tray menu, notifications); the timer keeps running.
+ */
+export type BackendError = { context: string; error: AppError }
+export type BarEdge = "top" | "bottom" | "left" | "right"
+export type CloseAction = 
+/**
+ * The timer keeps running in the tray; the window comes back with its state intact.
+ */
+"hideToTray" | "quit"
+/**
+ * The planner day changed. The timer announces it first, without `tasks`; the main window then rolls its tasks over with
+ * `roll_over_tasks`, which announces the day again with what it did for the Review workspace.
+ */
+export type DayRolledOver = { 
+/**
+ * `None` when the tasks are rolled over for the first time.
+ */
+from: string | null; to: string; tasks: RolloverSummary | null }
+/**
+ * When the planner's day begins; sessions after midnight but before this hour still count for the previous day.
+ */
+export type DaySettings = { 
+/**
+ * Local hour, 0 to 23; 0 starts the day at midnight.
+ */
+start_hour: number }
+/**
+ * Desktop-shell preferences that live next to `pomodoro_settings.json` but are not part of the timer settings.
+ */
+export type DesktopSettings = { tray: TraySettings; floating: FloatingSettings; rpc: RpcSettings; status_bar: StatusBarSettings; hooks: HookSettings; webhooks: Webhook[]; mqtt: MqttSettings; shortcuts: ShortcutSettings; drop_folder: DropFolderSettings; autostart: AutostartSettings; window: WindowSettings; day: DaySettings }
+/**
+ * A folder (for example a synced phone-notes folder) whose `.txt` and `.md` files are imported into the Inbox.
+ */
+export type DropFolderSettings = { enabled: boolean; path: string }
+/**
+ * Emitted to the `floating` window only, except `StatusBarHint` which goes to `main`.
+ */
+export type FloatingEvent = { kind: "preferencesChanged"; theme: string; opacity: number } | { kind: "modeChanged"; mode: FloatingMode } | { kind: "clickThroughChanged"; enabled: boolean } | 
+/**
+ * macOS has no floating window; the main view points at the menu bar item instead.
+ */
+{ kind: "statusBarHint" }
+export type FloatingMode = "standard" | "mini" | "bar"
+export type FloatingSettings = { snap_to_edges: boolean; 
+/**
+ * Logical pixels from a work-area edge within which the floating window snaps to it.
+ */
+snap_distance: number; 
+/**
+ * Native window opacity, kept in sync with the floating settings view.
+ */
+opacity: number; hide_during_breaks: boolean; show_on_work_start: boolean; hide_when_main_focused: boolean; 
+/**
+ * Screen edge the `bar` floating mode docks to.
+ */
+bar_edge: BarEdge; 
+/**
+ * Bar thickness in logical pixels.
+ */
+bar_thickness: number }
+export type Frequency = "daily" | "weekly" | "monthly" | "yearly"
+export type HookEvent = 
+/**
+ * The timer starts or resumes running a work session.
+ */
+"workStarted" | "workFinished" | 
+/**
+ * The timer starts or resumes running a short or long break.
+ */
+"breakStarted" | "breakFinished" | "goalReached"
+export type HookSettings = { commands: ShellHook[]; 
+/**
+ * Seconds after which a hook that is still running gets killed.
+ */
+timeout_secs: number }
+export type JsonValue = null | boolean | number | string | JsonValue[] | Partial<{ [key in string]: JsonValue }>
+/**
+ * Work for the main window's frontend, which owns tasks and navigation; queued because the window may still be loading.
+ */
+export type MainRequest = { kind: "navigate"; view: MainView } | { kind: "capture"; text: string } | 
+/**
+ * Binds the timer to the todo with this title, creating it in the Inbox when there is none.
+ */
+{ kind: "focusTask"; title: string } | 
+/**
+ * An Inbox item with notes: a file from the drop folder or a page clipped in the browser.
+ */
+{ kind: "importNote"; title: string; notes: string | null }
+/**
+ * Tells the main window to drain its request queue with `take_main_requests`.
+ */
+export type MainRequestsQueued = null
+export type MainView = "inbox" | "today" | "review" | 
+/**
+ * The Today workspace's calendar.
+ */
+"calendar"
+/**
+ * Broker connection for publishing the timer to home automation (for example Home Assistant).
+ */
+export type MqttSettings = { enabled: boolean; host: string; port: number; 
+/**
+ * Left empty for brokers that allow anonymous clients. The password is kept in the OS keyring (`set_mqtt_password`).
+ */
+username: string; 
+/**
+ * Topic segment in `daily-planner/<profile>/...`, so several machines can share a broker.
+ */
+profile: string; 
+/**
+ * Subscribes to `daily-planner/<profile>/set` and runs the commands published there.
+ */
+accept_commands: boolean }
+/**
+ * One occurrence for the calendar view.
+ */
+export type Occurrence = { 
+/**
+ * RFC 3339 with the offset in effect that day, so occurrences keep their wall-clock time across DST changes.
+ */
+start: string; 
+/**
+ * Local `YYYY-MM-DD`.
+ */
+date: string }
+export type PomodoroSettings = { work_duration: number; short_break_duration: number; long_break_duration: number; long_break_interval: number; auto_start_breaks: boolean; auto_start_pomodoros: boolean; max_sessions: number; stop_after_sessions: number; stop_after_long_break: boolean }
+export type PomodoroState = { time_left: number; is_active: boolean; mode: string; sessions_completed: number; last_date: string; settings: PomodoroSettings; current_task: string | null }
+/**
+ * An RFC 5545 `RRULE` with the `EXDATE`s of its series. Weeks start on Monday (`WKST=MO`).
+ */
+export type RecurrenceRule = { frequency: Frequency; 
+/**
+ * Every nth day, week, month or year; 0 is read as 1.
+ */
+interval?: number; by_day?: RuleDay[]; 
+/**
+ * Days of the month, negative from the end (`-1` is the last day). Months without the day are skipped, so `31` never falls in April.
+ */
+by_month_day?: number[]; 
+/**
+ * Months 1 to 12.
+ */
+by_month?: number[]; count: number | null; 
+/**
+ * Last possible start, inclusive: a date covers that whole day, a local date-time or RFC 3339 instant is exact.
+ */
+until: string | null; 
+/**
+ * `EXDATE`s: a date removes that day's occurrences, a date-time only the one starting then. Removed occurrences still count towards `count`.
+ */
+exceptions?: string[]; 
+/**
+ * Only days the workday calendar marks as working. Unlike exceptions, skipped days do not count towards `count`.
+ */
+workdays_only?: boolean }
+/**
+ * The main window's tasks rolled over to a new planner day, for it to merge into its store.
+ */
+export type RolledOverTasks = { date: string; 
+/**
+ * The tasks that were sent, in order and with the rollover's changes, followed by the new instances.
+ */
+tasks: Partial<{ [key in string]: JsonValue }>[]; summary: RolloverSummary }
+/**
+ * What a rollover did, by task ID.
+ */
+export type RolloverSummary = { 
+/**
+ * Unfinished commitments from before the previous rollover, still waiting for review.
+ */
+carried_forward: string[]; 
+/**
+ * Unfinished commitments from the days that just ended, including the instances generated for them.
+ */
+pending_review: string[]; 
+/**
+ * New instances of recurring tasks: today's and those of the days that passed without a rollover.
+ */
+generated: string[] }
+export type RpcSettings = { 
+/**
+ * Off by default, since the server lets any local process of this user drive the timer.
+ */
+enabled: boolean; 
+/**
+ * Localhost TCP port on platforms without the Unix socket; 0 picks a free one. The port and token are written to `rpc_endpoint.json`.
+ */
+port: number }
+/**
+ * A `BYDAY` entry. With `nth` only that occurrence of the weekday in the month (in the year for yearly rules without `by_month`); negative counts from the end, so `-1` Friday is the last Friday.
+ */
+export type RuleDay = { weekday: RuleWeekday; nth: number | null }
+export type RuleWeekday = "monday" | "tuesday" | "wednesday" | "thursday" | "friday" | "saturday" | "sunday"
+export type SettingsEvent = ({ kind: "pomodoroChanged" } & PomodoroSettings) | ({ kind: "desktopChanged" } & DesktopSettings)
+export type ShellHook = { event: HookEvent; 
+/**
+ * Run through `sh -c` (`cmd /C` on Windows) with `DAILY_PLANNER_*` variables set and the event as JSON on stdin.
+ */
+command: string }
+/**
+ * System-wide hotkeys in accelerator syntax (`CommandOrControl+Shift+P`); empty leaves an action unbound.
+ */
+export type ShortcutSettings = { toggle_timer: string; skip: string; reset: string; 
+/**
+ * Shows the floating window, or hides it when it is already visible.
+ */
+toggle_floating: string; quick_capture: string }
+export type StatusBarFormat = 
+/**
+ * One JSON object per update with `text`, `tooltip`, `class` and `percentage`, for `return-type: json`.
+ */
+"waybar" | "polybar" | "i3blocks"
+/**
+ * Timer output for Linux status bars, for setups where the tray indicator is missing or broken.
+ */
+export type StatusBarSettings = { enabled: boolean; format: StatusBarFormat; 
+/**
+ * Output path; empty uses `daily-planner-<user>.status` next to the control socket.
+ */
+path: string; 
+/**
+ * Streams one line per update into a FIFO (for `exec`/`tail`/`persist` modules) instead of rewriting a file.
+ */
+fifo: boolean }
+/**
+ * Emitted to every window: the timer is rendered in the main view, the floating window and the tray.
+ */
+export type TimerEvent = ({ kind: "tick" } & PomodoroState) | { kind: "workCompleted"; work_duration: number } | { kind: "breakCompleted" } | 
+/**
+ * The `stop_after_sessions` goal was met and the timer stopped.
+ */
+{ kind: "goalReached"; sessions_completed: number }
+export type TrayClickAction = "none" | "toggleTimer" | "openMain" | "openFloating"
+/**
+ * Tauri reports no tray icon clicks on Linux, so the click actions have no effect there; the tray menu opens instead.
+ */
+export type TraySettings = { single_click: TrayClickAction; double_click: TrayClickAction; middle_click: TrayClickAction; 
+/**
+ * Shows the countdown next to the tray icon (menu bar title on macOS, indicator label on Linux).
+ */
+show_countdown: boolean }
+export type Webhook = { url: string; 
+/**
+ * Transitions that are posted to `url`; empty posts all of them.
+ */
+events?: HookEvent[] }
+/**
+ * What closing and minimizing the main window do, and how quitting is confirmed.
+ */
+export type WindowSettings = { close_action: CloseAction; minimize_to_tray: boolean; 
+/**
+ * Asks before quitting while a work session is running.
+ */
+confirm_quit_during_work: boolean }
+/**
+ * The frontend's holiday configuration (`holidays.ts`): holidays are off and `workdays` are weekend days that are worked instead.
+ */
+export type WorkdayCalendar = { holidays?: string[]; workdays?: string[] }
+
+/** tauri-specta globals **/
+
+import {
+	invoke as TAURI_INVOKE,
+	Channel as TAURI_CHANNEL,
+} from "@tauri-apps/api/core";
+import * as TAURI_API_EVENT from "@tauri-apps/api/event";
+import { type WebviewWindow as __WebviewWindow__ } from "@tauri-apps/api/webviewWindow";
+
+type __EventObj__<T> = {
+	listen: (
+		cb: TAURI_API_EVENT.EventCallback<T>,
+	) => ReturnType<typeof TAURI_API_EVENT.listen<T>>;
+	once: (
+		cb: TAURI_API_EVENT.EventCallback<T>,
+	) => ReturnType<typeof TAURI_API_EVENT.once<T>>;
+	emit: null extends T
+		? (payload?: T) => ReturnType<typeof TAURI_API_EVENT.emit>
+		: (payload: T) => ReturnType<typeof TAURI_API_EVENT.emit>;
+};
+
+export type Result<T, E> =
+	| { status: "ok"; data: T }
+	| { status: "error"; error: E };
+
+function __makeEvents__<T extends Record<string, any>>(
+	mappings: Record<keyof T, string>,
+) {
+	return new Proxy(
+		{} as unknown as {
+			[K in keyof T]: __EventObj__<T[K]> & {
+				(handle: __WebviewWindow__): __EventObj__<T[K]>;
+			};
+		},
+		{
+			get: (_, event) => {
+				const name = mappings[event as keyof T];
+
+				return new Proxy((() => {}) as any, {
+					apply: (_, __, [window]: [__WebviewWindow__]) => ({
+						listen: (arg: any) => window.listen(name, arg),
+						once: (arg: any) => window.once(name, arg),
+						emit: (arg: any) => window.emit(name, arg),
+					}),
+					get: (_, command: keyof __EventObj__<any>) => {
+						switch (command) {
+							case "listen":
+								return (arg: any) => TAURI_API_EVENT.listen(name, arg);
+							case "once":
+								return (arg: any) => TAURI_API_EVENT.once(name, arg);
+							case "emit":
+								return (arg: any) => TAURI_API_EVENT.emit(name, arg);
+						}
+					},
+				});
+			},
+		},
+	);
+}
@@ -10,10 +10,15 @@ import enLocale from '@fullcalendar/core/locales/en-gb';
 import deLocale from '@fullcalendar/core/locales/de';
 import zhLocale from '@fullcalendar/core/locales/zh-cn';
 import { Check, ChevronLeft, ChevronRight, Filter, Flag, Plus, Search, Target } from 'lucide-react';
-import { addMinutes, format, parseISO } from 'date-fns';
+import { addDays, addMinutes, differenceInMinutes, format, getDaysInMonth, parseISO } from 'date-fns';
+import { commands, type RecurrenceRule } from '../bindings';
 import { useI18n } from '../i18n';
 import { useAppStore } from '../stores/useAppStore';
 import { PlannerList, Task, TaskPriority, TaskStatus, WeeklyGoal } from '../types';
+import { logCommandError, unwrapResult } from '../utils/commandResult';
+import { getHolidayConfig } from '../utils/holidays';
+import { isTauriRuntime } from '../utils/runtime';
+import { getPlannerDate } from '../utils/plannerDate';
 import { getTaskDateLabel, isTodayTask } from '../utils/taskActivity';
 import { getPlannerWeek, getPlannerWeekYear } from '../utils/week';
 import { Button } from './ui/button';
@@ -87,8 +92,21 @@ const normalizeDateTimeInput = (value: string) => (value ? `${value}:00` : undef
 const inferPlanningState = (start?: string, dueAt?: string): Task['planningState'] => {
   if (!start && !dueAt) return 'inbox';
   const date = parseISO(start || dueAt || new Date().toISOString());
-  const today = format(new Date(), 'yyyy-MM-dd');
-  return format(date, 'yyyy-MM-dd') <= today ? 'today' : 'later';
+  return format(date, 'yyyy-MM-dd') <= getPlannerDate() ? 'today' : 'later';
+};
+
+// Same reading of `TaskRecurrence` as the backend's day rollover: a monthly series on a month's last day keeps to last days.
+const toRecurrenceRule = (task: Task, anchor: Date): RecurrenceRule | null => {
+  const recurrence = task.recurrence;
+  if (!recurrence || recurrence.frequency === 'none') return null;
+  const monthEnd = recurrence.frequency === 'monthly' && anchor.getDate() === getDaysInMonth(anchor);
+  return {
+    frequency: recurrence.frequency,
+    by_month_day: monthEnd ? [-1] : [],
+    count: null,
+    until: recurrence.endDate || null,
+    workdays_only: recurrence.smartWorkdayOnly,
+  };
 };
 
 const createEmptyTask = (): Task => {
@@ -135,6 +153,8 @@ const CalendarView = () => {
   const [listFilter, setListFilter] = useState<'all' | string>('all');
   const [dialogOpen, setDialogOpen] = useState(false);
   const [draft, setDraft] = useState<Task>(createEmptyTask());
+  const [visibleRange, setVisibleRange] = useState<{ from: string; to: string } | null>(null);
+  const [occurrenceEvents, setOccurrenceEvents] = useState<EventInput[]>([]);
 
   const viewOptions = useMemo(() => ([
     ['timeGridDay', t('calendar.dayView')],
@@ -210,6 +230,56 @@ const CalendarView = () => {
     };
   }), [filteredTasks, listById]);
 
+  // Later occurrences of recurring tasks, drawn as read-only previews; the rollover creates the real task on the day,
+  // so days up to the series' `lastGeneratedDate` already show theirs.
+  useEffect(() => {
+    if (!visibleRange || !isTauriRuntime()) return undefined;
+    let cancelled = false;
+    const recurring = filteredTasks.filter((task) => task.status !== 'archived' && (task.scheduledStart || task.dueAt) && task.recurrence && task.recurrence.frequency !== 'none');
+    const calendar = getHolidayConfig();
+
+    Promise.all(recurring.map(async (task) => {
+      const start = (task.scheduledStart || task.dueAt) as string;
+      const rule = toRecurrenceRule(task, parseISO(start));
+      if (!rule) return [];
+      const occurrences = unwrapResult(await commands.expandRecurrence(rule, start, visibleRange.from, visibleRange.to, calendar));
+      const list = listById.get(task.listId);
+      const listColor = list?.color || '#2563eb';
+      const minutes = task.scheduledEnd ? differenceInMinutes(parseISO(task.scheduledEnd), parseISO(start)) : 0;
+      const shownUntil = task.recurrence?.lastGeneratedDate || format(parseISO(start), 'yyyy-MM-dd');
+      return occurrences
+        .filter((occurrence) => occurrence.date > shownUntil)
+        .map((occurrence): EventInput => ({
+          id: `${task.id}@${occurrence.date}`,
+          title: task.title,
+          start: occurrence.start,
+          end: minutes > 0 ? addMinutes(parseISO(occurrence.start), minutes) : undefined,
+          allDay: task.allDay,
+          editable: false,
+          backgroundColor: `${listColor}11`,
+          borderColor: listColor,
+          textColor: '#0f172a',
+          classNames: ['task-occurrence', priorityClass[task.priority]],
+          extendedProps: {
+            priority: task.priority,
+            listColor,
+            listName: list?.name || 'Inbox',
+            taskId: task.id,
+          },
+        }));
+    }))
+      .then((groups) => {
+        if (!cancelled) setOccurrenceEvents(groups.flat());
+      })
+      .catch(logCommandError);
+
+    return () => {
+      cancelled = true;
+    };
+  }, [filteredTasks, listById, visibleRange]);
+
+  const calendarEvents = useMemo(() => [...events, ...occurrenceEvents], [events, occurrenceEvents]);
+
   const selectedList = (id: string): PlannerList | undefined => lists.find((item) => item.id === id);
 
   const openTaskDialog = (task?: Task) => {
@@ -293,7 +363,7 @@ const CalendarView = () => {
 
     return (
       <div className="flex min-w-0 items-center gap-2 overflow-hidden rounded-xl px-1 py-0.5">
-        <button
+        {task ? <button
           type="button"
           data-testid={`calendar-quick-complete-${arg.event.id}`}
           className={`flex h-5 w-5 shrink-0 items-center justify-center rounded-full border text-[10px] font-bold transition ${
@@ -310,7 +380,7 @@ const CalendarView = () => {
           }}
         >
           <Check size={12} strokeWidth={3} />
-        </button>
+        </button> : null}
         <span className={`h-2 w-2 shrink-0 rounded-full ${priority === 'high' ? 'bg-rose-500' : priority === 'medium' ? 'bg-amber-500' : 'bg-slate-400'}`} />
         <div className="min-w-0">
           <div className="truncate text-xs font-semibold">{arg.event.title}</div>
@@ -459,14 +529,17 @@ const CalendarView = () => {
           dayMaxEvents={3}
           slotMinTime="06:00:00"
           slotMaxTime="23:00:00"
-          events={events}
+          events={calendarEvents}
           eventContent={renderEventContent}
           select={handleSelect}
           eventDrop={(arg: EventDropArg) => updateCalendarTaskTime(arg.event.id, arg.event.start, arg.event.end, arg.event.allDay)}
           eventResize={(arg: EventResizeDoneArg) => updateCalendarTaskTime(arg.event.id, arg.event.start, arg.event.end, arg.event.allDay)}
-          datesSet={(arg: DatesSetArg) => setCalendarTitle(arg.view.title)}
+          datesSet={(arg: DatesSetArg) => {
+            setCalendarTitle(arg.view.title);
+            setVisibleRange({ from: format(arg.start, 'yyyy-MM-dd'), to: format(addDays(arg.end, -1), 'yyyy-MM-dd') });
+          }}
           eventClick={(arg: EventClickArg) => {
-            const task = taskById.get(arg.event.id);
+            const task = taskById.get(arg.event.extendedProps.taskId || arg.event.id);
             if (task) openTaskDialog(task);
           }}
         />
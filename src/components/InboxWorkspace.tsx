@@ -5,32 +5,11 @@ import { useI18n } from '../i18n';
 import { applyActionPreview } from '../services/aiActions';
 import { useAppStore } from '../stores/useAppStore';
 import { Task } from '../types';
-import { isInboxTask, isLaterTask } from '../utils/taskActivity';
+import { createInboxTask, isInboxTask, isLaterTask } from '../utils/taskActivity';
 import WorkflowSuggestionCard from './WorkflowSuggestionCard';
 import { Button } from './ui/button';
 import { Input } from './ui/input';
 
-const createInboxTask = (title: string): Task => {
-  const now = new Date().toISOString();
-  return {
-    id: crypto.randomUUID(),
-    title,
-    status: 'todo',
-    planningState: 'inbox',
-    reviewStatus: 'pending',
-    allDay: false,
-    priority: 'medium',
-    listId: 'inbox',
-    tagIds: [],
-    linkedGoalIds: [],
-    linkedWeeklyGoalIds: [],
-    pomodoroSessions: 0,
-    pomodoroMinutes: 0,
-    createdAt: now,
-    updatedAt: now,
-  };
-};
-
 const durationOptions: Array<NonNullable<Task['estimatedMinutes']>> = [15, 30, 60, 90];
 const typeOptions: Array<NonNullable<Task['taskType']>> = ['deep', 'shallow', 'personal'];
 
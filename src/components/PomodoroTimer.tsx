@@ -1,13 +1,14 @@
 import { useEffect, useMemo, useState } from 'react';
-import { format } from 'date-fns';
 import { BarChart3, FastForward, Monitor, Pause, Play, RotateCcw, Settings2 } from 'lucide-react';
-import { invoke } from '@tauri-apps/api/core';
 import { useI18n } from '../i18n';
 import { usePomodoro } from '../contexts/PomodoroContext';
 import { useHoldAction } from '../hooks/useHoldAction';
 import { useAppStore } from '../stores/useAppStore';
 import { readFloatingMode, readFloatingSize } from '../utils/floatingWindow';
+import { commands } from '../bindings';
+import { logCommandError, unwrapResult } from '../utils/commandResult';
 import { isTauriRuntime } from '../utils/runtime';
+import { usePlannerDate } from '../utils/plannerDate';
 import { Button } from './ui/button';
 import { Dialog, DialogContent, DialogFooter, DialogHeader, DialogTitle } from './ui/dialog';
 import { Input } from './ui/input';
@@ -36,7 +37,7 @@ const PomodoroTimer = () => {
   const tasks = useAppStore((state) => state.tasks);
   const currentTaskId = useAppStore((state) => state.currentTaskId);
   const setCurrentTaskId = useAppStore((state) => state.setCurrentTaskId);
-  const todayKey = format(new Date(), 'yyyy-MM-dd');
+  const todayKey = usePlannerDate();
   const todayStats = useAppStore((state) => state.pomodoroHistory[todayKey]);
   const totalFocus = useAppStore((state) => Object.values(state.pomodoroHistory).reduce((sum, item) => sum + item.minutes, 0));
   const [settingsOpen, setSettingsOpen] = useState(false);
@@ -45,7 +46,7 @@ const PomodoroTimer = () => {
   useEffect(() => {
     setPlatform(inferBrowserPlatform());
     if (!isTauriRuntime()) return;
-    invoke<string>('get_runtime_platform').then(setPlatform).catch(() => undefined);
+    commands.getRuntimePlatform().then(unwrapResult).then(setPlatform).catch(() => undefined);
   }, []);
 
   const minutes = Math.floor(timeLeft / 60).toString().padStart(2, '0');
@@ -59,7 +60,7 @@ const PomodoroTimer = () => {
   const openFloating = () => {
     const mode = readFloatingMode();
     const size = readFloatingSize(mode);
-    invoke('toggle_floating_window', { mode, width: size.width, height: size.height }).catch(() => undefined);
+    commands.toggleFloatingWindow(mode, size.width, size.height).then(unwrapResult).catch(logCommandError);
   };
 
   const modeLabel = mode === 'work' ? t('pomodoro.work') : mode === 'shortBreak' ? t('pomodoro.shortBreak') : t('pomodoro.longBreak');
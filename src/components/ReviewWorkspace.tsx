@@ -8,6 +8,8 @@ import { useI18n } from '../i18n';
 import { applyActionPreview } from '../services/aiActions';
 import { useAppStore } from '../stores/useAppStore';
 import { Task } from '../types';
+import { usePlannerDate } from '../utils/plannerDate';
+import { onRolledOver } from '../utils/rollover';
 import { getPlanningState, getTaskReviewDate } from '../utils/taskActivity';
 import AIAssistant from './AIAssistant';
 import QuarterlyGoals from './QuarterlyGoals';
@@ -54,7 +56,7 @@ const ReviewWorkspace = () => {
   const rememberReviewDate = useAppStore((state) => state.rememberReviewDate);
   const { showFeedback } = useFeedback();
 
-  const today = format(new Date(), 'yyyy-MM-dd');
+  const today = usePlannerDate();
   const [selectedReviewDate, setSelectedReviewDate] = useState(today);
   const [reviewAssistantMode, setReviewAssistantMode] = useState<'shutdown' | 'nextWeek'>('shutdown');
   const [reviewSection, setReviewSection] = useState<'daily' | 'weekly' | 'plan' | 'goals' | 'chat'>('daily');
@@ -63,6 +65,19 @@ const ReviewWorkspace = () => {
     rememberReviewDate(selectedReviewDate);
   }, [rememberReviewDate, selectedReviewDate]);
 
+  // Opens the newest day that just got commitments to review; those carried forward were announced before.
+  useEffect(() => onRolledOver(({ summary }) => {
+    if (summary.pending_review.length === 0) return;
+    const pending = new Set(summary.pending_review);
+    const [newest] = useAppStore.getState().tasks
+      .filter((task) => pending.has(task.id))
+      .map((task) => getTaskReviewDate(task))
+      .sort((a, b) => b.localeCompare(a));
+    if (newest) setSelectedReviewDate(newest);
+    setReviewSection('daily');
+    showFeedback({ message: reviewStateCopy.feedback.rolledOver(summary.pending_review.length) });
+  }), [reviewStateCopy, showFeedback]);
+
   const reviewableDates = useMemo(
     () => Array.from(new Set([
       today,
@@ -1,7 +1,6 @@
 import { useCallback, useEffect, useRef, useState } from 'react';
 import { Database, Download, FolderTree, Info, Plus, RefreshCcw, Settings as SettingsIcon, Trash2, Upload } from 'lucide-react';
 import { getVersion } from '@tauri-apps/api/app';
-import { invoke } from '@tauri-apps/api/core';
 import { open, save } from '@tauri-apps/plugin-dialog';
 import { readTextFile, writeTextFile } from '@tauri-apps/plugin-fs';
 import { useFeedback } from '../contexts/FeedbackContext';
@@ -9,6 +8,8 @@ import { useI18n } from '../i18n';
 import { useAppStore } from '../stores/useAppStore';
 import { PlannerList } from '../types';
 import { checkForUpdates, RELEASES_URL, relaunchApp, supportsUpdater } from '../services/updater';
+import { commands } from '../bindings';
+import { unwrapResult } from '../utils/commandResult';
 import { isTauriRuntime } from '../utils/runtime';
 import { Button } from './ui/button';
 import { Dialog, DialogContent, DialogDescription, DialogHeader, DialogTitle } from './ui/dialog';
@@ -100,7 +101,7 @@ const Settings = ({ isOpen, onClose }: SettingsProps) => {
   useEffect(() => {
     setPlatform(inferBrowserPlatform());
     if (!isTauriRuntime()) return;
-    invoke<string>('get_runtime_platform').then(setPlatform).catch(() => undefined);
+    commands.getRuntimePlatform().then(unwrapResult).then(setPlatform).catch(() => undefined);
   }, []);
 
   const handleExport = async () => {
@@ -1,6 +1,5 @@
 import { Suspense, lazy, useEffect, useMemo, useState } from 'react';
 import { addMinutes, format, parseISO } from 'date-fns';
-import { invoke } from '@tauri-apps/api/core';
 import { ArrowRight, CheckCircle2, ChevronDown, Clock3, Coffee, Flame, Monitor, Pause, Play, RotateCcw, Sparkles, Timer } from 'lucide-react';
 import { getFocusCompanionCopy } from '../content/focusCompanionCopy';
 import { getTodayStateCopy } from '../content/todayStateCopy';
@@ -13,7 +12,10 @@ import { useAppStore } from '../stores/useAppStore';
 import { Task } from '../types';
 import { readFloatingMode, readFloatingSize } from '../utils/floatingWindow';
 import { buildFocusSessionBrief, getFocusRhythmPreset, getRecommendedFocusRhythm } from '../utils/focusRhythm';
+import { commands } from '../bindings';
+import { logCommandError, unwrapResult } from '../utils/commandResult';
 import { isTauriRuntime } from '../utils/runtime';
+import { getPlannerDate, usePlannerDate } from '../utils/plannerDate';
 import { getPlanningState, getTaskDateLabel, isLaterTask, isTodayTask } from '../utils/taskActivity';
 import WorkflowSuggestionCard from './WorkflowSuggestionCard';
 import { Button } from './ui/button';
@@ -50,8 +52,7 @@ const quickScheduleSlots: Array<{ id: QuickScheduleSlot; hour: number }> = [
 const inferPlanningState = (start?: string, dueAt?: string): Task['planningState'] => {
   if (!start && !dueAt) return 'inbox';
   const date = parseISO(start || dueAt || new Date().toISOString());
-  const today = format(new Date(), 'yyyy-MM-dd');
-  return format(date, 'yyyy-MM-dd') <= today ? 'today' : 'later';
+  return format(date, 'yyyy-MM-dd') <= getPlannerDate() ? 'today' : 'later';
 };
 
 const buildDefaultSchedule = (task: Task) => {
@@ -303,7 +304,12 @@ const TaskChip = ({
   );
 };
 
-const TodayWorkspace = () => {
+type TodayWorkspaceProps = {
+  /** Switches to this view whenever a new request object arrives, e.g. from a tray item or deep link. */
+  viewRequest?: { view: 'plan' | 'calendar' } | null;
+};
+
+const TodayWorkspace = ({ viewRequest = null }: TodayWorkspaceProps) => {
   const { locale, t } = useI18n();
   const workflowCopy = getWorkflowCopy(locale);
   const copy = workflowCopy.today;
@@ -323,7 +329,7 @@ const TodayWorkspace = () => {
   const deleteTask = useAppStore((state) => state.deleteTask);
   const { timeLeft, isActive, mode, pomodoroSettings, updatePomodoroSettings, toggleTimer, resetTimer, currentTaskName } = usePomodoro();
   const { showFeedback } = useFeedback();
-  const [view, setView] = useState<'plan' | 'calendar'>('plan');
+  const [view, setView] = useState<'plan' | 'calendar'>(viewRequest?.view ?? 'plan');
   const [sidebarMode, setSidebarMode] = useState<TodaySidebarMode>('focus');
   const [todayAssistantMode, setTodayAssistantMode] = useState<'plan' | 'focus'>('plan');
   const [scheduleTask, setScheduleTask] = useState<Task | null>(null);
@@ -334,9 +340,13 @@ const TodayWorkspace = () => {
   useEffect(() => {
     setPlatform(inferBrowserPlatform());
     if (!isTauriRuntime()) return;
-    invoke<string>('get_runtime_platform').then(setPlatform).catch(() => undefined);
+    commands.getRuntimePlatform().then(unwrapResult).then(setPlatform).catch(() => undefined);
   }, []);
 
+  useEffect(() => {
+    if (viewRequest) setView(viewRequest.view);
+  }, [viewRequest]);
+
   const todayTasks = useMemo(() => tasks.filter((task) => isTodayTask(task)), [tasks]);
   const laterTasks = useMemo(() => tasks.filter((task) => isLaterTask(task)), [tasks]);
   const activeGoals = useMemo(() => goals.filter((goal) => !goal.isCompleted).slice(0, 3), [goals]);
@@ -362,7 +372,7 @@ const TodayWorkspace = () => {
   const activeFocusTask = tasks.find((task) => task.id === currentTaskId) || (!currentTaskName ? highlightTask : null);
   const activeFocusTaskTitle = currentTaskName || activeFocusTask?.title || focusCopy.noTask;
   const timerLabel = `${Math.floor(timeLeft / 60).toString().padStart(2, '0')}:${(timeLeft % 60).toString().padStart(2, '0')}`;
-  const todayKey = format(new Date(), 'yyyy-MM-dd');
+  const todayKey = usePlannerDate();
   const todayPomodoroStats = useAppStore((state) => state.pomodoroHistory[todayKey]);
   const completedToday = useMemo(
     () => tasks.filter((task) => task.status === 'done' && task.completedAt?.slice(0, 10) === todayKey).length,
@@ -454,7 +464,7 @@ const TodayWorkspace = () => {
 
     const mode = readFloatingMode();
     const size = readFloatingSize(mode);
-    invoke('toggle_floating_window', { mode, width: size.width, height: size.height }).catch(() => undefined);
+    commands.toggleFloatingWindow(mode, size.width, size.height).then(unwrapResult).catch(logCommandError);
   };
 
   const openScheduleDialog = (task: Task) => {
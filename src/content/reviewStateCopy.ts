@@ -6,6 +6,7 @@ type ReviewStateCopy = {
     completed: (title: string) => string;
     movedToLater: (title: string) => string;
     dropped: (title: string) => string;
+    rolledOver: (pending: number) => string;
   };
   labels: {
     completedForDate: (date: string, fallback: string) => string;
@@ -21,6 +22,7 @@ const zhCN: ReviewStateCopy = {
     completed: (title) => `\u5df2\u5b8c\u6210\uff1a${title}`,
     movedToLater: (title) => `\u5df2\u79fb\u5230\u7a0d\u540e\uff1a${title}`,
     dropped: (title) => `\u5df2\u653e\u5f03\uff1a${title}`,
+    rolledOver: (pending) => `\u65b0\u7684\u4e00\u5929\u5f00\u59cb\u4e86\uff0c\u8fd8\u6709 ${pending} \u9879\u5f85\u590d\u76d8`,
   },
   labels: {
     completedForDate: (date, fallback) => (date ? `${date} \u5df2\u5b8c\u6210` : fallback),
@@ -36,6 +38,7 @@ const en: ReviewStateCopy = {
     completed: (title) => `Completed: ${title}`,
     movedToLater: (title) => `Moved to later: ${title}`,
     dropped: (title) => `Dropped: ${title}`,
+    rolledOver: (pending) => `A new day has started; ${pending} ${pending === 1 ? 'item is' : 'items are'} waiting for review`,
   },
   labels: {
     completedForDate: (date, fallback) => (date ? `Completed on ${date}` : fallback),
@@ -51,6 +54,7 @@ const de: ReviewStateCopy = {
     completed: (title) => `Erledigt: ${title}`,
     movedToLater: (title) => `Auf sp\u00e4ter verschoben: ${title}`,
     dropped: (title) => `Verworfen: ${title}`,
+    rolledOver: (pending) => `Ein neuer Tag hat begonnen; ${pending} ${pending === 1 ? 'Aufgabe wartet' : 'Aufgaben warten'} auf den R\u00fcckblick`,
   },
   labels: {
     completedForDate: (date, fallback) => (date ? `Erledigt am ${date}` : fallback),
@@ -1,6 +1,9 @@
 import { createContext, ReactNode, useCallback, useContext, useEffect, useMemo, useRef, useState } from 'react';
+import { events } from '../bindings';
 import { Button } from '../components/ui/button';
 import { useI18n } from '../i18n';
+import { describeAppError } from '../utils/commandResult';
+import { isTauriRuntime } from '../utils/runtime';
 
 type FeedbackOptions = {
   message: string;
@@ -30,6 +33,16 @@ export const FeedbackProvider = ({ children }: { children: ReactNode }) => {
     setFeedback({ id: idRef.current, ...options });
   }, []);
 
+  useEffect(() => {
+    if (!isTauriRuntime()) return undefined;
+    const unlisten = events.backendError.listen(({ payload }) => {
+      showFeedback({ message: `${payload.context} — ${describeAppError(payload.error)}` });
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [showFeedback]);
+
   useEffect(() => {
     if (!feedback) return undefined;
     const timer = window.setTimeout(() => setFeedback((current) => (current?.id === feedback.id ? null : current)), 4000);
@@ -1,32 +1,12 @@
 /* eslint-disable react-refresh/only-export-components */
 import React, { createContext, useCallback, useContext, useEffect, useMemo, useState } from 'react';
 import { format } from 'date-fns';
-import { invoke } from '@tauri-apps/api/core';
-import { listen } from '@tauri-apps/api/event';
+import { commands, events, type PomodoroState as NativePomodoroState } from '../bindings';
+import { logCommandError, unwrapResult } from '../utils/commandResult';
 import { useAppStore } from '../stores/useAppStore';
 import { PomodoroMode, PomodoroSettings } from '../types';
 import { isTauriRuntime } from '../utils/runtime';
 
-type NativePomodoroState = {
-  time_left: number;
-  is_active: boolean;
-  mode: string;
-  sessions_completed: number;
-  last_date: string;
-  current_task?: string | null;
-  settings: {
-    work_duration: number;
-    short_break_duration: number;
-    long_break_duration: number;
-    long_break_interval: number;
-    auto_start_breaks: boolean;
-    auto_start_pomodoros: boolean;
-    max_sessions: number;
-    stop_after_sessions: number;
-    stop_after_long_break: boolean;
-  };
-};
-
 type PomodoroContextValue = {
   pomodoroSettings: PomodoroSettings;
   updatePomodoroSettings: (settings: Partial<PomodoroSettings>) => void;
@@ -113,7 +93,7 @@ export const PomodoroProvider: React.FC<{ children: React.ReactNode }> = ({ chil
     const currentTask = currentTaskId ? tasks.find((task) => task.id === currentTaskId) : null;
     setState((current) => current ? { ...current, current_task: currentTask?.title || null } : current);
     if (!isTauriRuntime()) return;
-    invoke('update_task_name', { name: currentTask?.title || null }).catch(() => undefined);
+    commands.updateTaskName(currentTask?.title || null).then(unwrapResult).catch(logCommandError);
   }, [currentTaskId, tasks]);
 
   const syncCompletedSessions = useCallback((nextState: NativePomodoroState | null) => {
@@ -135,7 +115,8 @@ export const PomodoroProvider: React.FC<{ children: React.ReactNode }> = ({ chil
       return undefined;
     }
 
-    invoke<NativePomodoroState>('get_pomodoro_state')
+    commands.getPomodoroState()
+      .then(unwrapResult)
       .then((value) => {
         setState(value);
         syncCompletedSessions(value);
@@ -144,21 +125,19 @@ export const PomodoroProvider: React.FC<{ children: React.ReactNode }> = ({ chil
         setState((current) => current || buildFallbackState(localSettings, useAppStore.getState().currentTaskId ? useAppStore.getState().tasks.find((task) => task.id === useAppStore.getState().currentTaskId)?.title : null));
       });
 
-    const unlistenTick = listen<NativePomodoroState>('pomodoro_tick', (event) => {
-      setState(event.payload);
-      syncCompletedSessions(event.payload);
-    });
-    const unlistenCompleted = listen('pomodoro_completed', () => {
-      if (useAppStore.getState().pomodoroSettings.playSound) playAudio('/sounds/complete.wav');
-    });
-    const unlistenBreak = listen('break_completed', () => {
-      if (useAppStore.getState().pomodoroSettings.playSound) playAudio('/sounds/start.wav');
+    const unlistenTimer = events.timerEvent.listen(({ payload }) => {
+      if (payload.kind === 'tick') {
+        setState(payload);
+        syncCompletedSessions(payload);
+      } else if (payload.kind === 'workCompleted') {
+        if (useAppStore.getState().pomodoroSettings.playSound) playAudio('/sounds/complete.wav');
+      } else if (useAppStore.getState().pomodoroSettings.playSound) {
+        playAudio('/sounds/start.wav');
+      }
     });
 
     return () => {
-      unlistenTick.then((fn) => fn());
-      unlistenCompleted.then((fn) => fn());
-      unlistenBreak.then((fn) => fn());
+      unlistenTimer.then((fn) => fn());
     };
   }, [buildFallbackState, localSettings, syncCompletedSessions]);
 
@@ -197,9 +176,7 @@ export const PomodoroProvider: React.FC<{ children: React.ReactNode }> = ({ chil
       return;
     }
 
-    invoke('update_settings', {
-      settings: buildNativeSettings(merged),
-    }).catch(() => undefined);
+    commands.updateSettings(buildNativeSettings(merged)).then(unwrapResult).catch(logCommandError);
   }, [buildFallbackState, buildNativeSettings, updateStoreSettings]);
 
   const value = useMemo<PomodoroContextValue>(() => ({
@@ -231,7 +208,7 @@ export const PomodoroProvider: React.FC<{ children: React.ReactNode }> = ({ chil
         });
         return;
       }
-      invoke('toggle_timer').catch(() => undefined);
+      commands.toggleTimer().then(unwrapResult).catch(logCommandError);
     },
     resetTimer: () => {
       if (!isTauriRuntime()) {
@@ -242,14 +219,14 @@ export const PomodoroProvider: React.FC<{ children: React.ReactNode }> = ({ chil
         });
         return;
       }
-      invoke('reset_timer').catch(() => undefined);
+      commands.resetTimer().then(unwrapResult).catch(logCommandError);
     },
     skipMode: () => {
       if (!isTauriRuntime()) {
         setState((current) => nextModeAfterSkip(current || buildFallbackState(useAppStore.getState().pomodoroSettings, useAppStore.getState().tasks.find((task) => task.id === useAppStore.getState().currentTaskId)?.title)));
         return;
       }
-      invoke('skip_mode').catch(() => undefined);
+      commands.skipMode().then(unwrapResult).catch(logCommandError);
     },
   }), [buildFallbackState, localSettings, nextModeAfterSkip, state, updatePomodoroSettings]);
 
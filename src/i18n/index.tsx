@@ -663,6 +663,21 @@ Object.assign(baseDe, {
   'ai.error': 'KI-Anfrage fehlgeschlagen. Bitte Konfiguration prüfen und erneut versuchen.',
 });
 
+Object.assign(baseZh, {
+  'capture.placeholder': '记下一件事，回车放入收件箱',
+  'capture.hint': 'Enter 保存 · Esc 关闭',
+});
+
+Object.assign(baseEn, {
+  'capture.placeholder': 'Jot something down, press Enter to send it to the Inbox',
+  'capture.hint': 'Enter to save · Esc to close',
+});
+
+Object.assign(baseDe, {
+  'capture.placeholder': 'Kurz notieren, mit Enter in den Eingang legen',
+  'capture.hint': 'Enter speichert · Esc schließt',
+});
+
 const messages: Record<AppLocale, Messages> = { 'zh-CN': baseZh, en: baseEn, de: baseDe };
 
 const detectLocale = (): AppLocale => {
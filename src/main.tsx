@@ -5,6 +5,7 @@ import { PomodoroProvider } from './contexts/PomodoroContext';
 import { I18nProvider } from './i18n';
 import './index.css';
 import FloatingPomodoro from './views/FloatingPomodoro.tsx';
+import { startPlannerDateSync } from './utils/plannerDate';
 
 const params = typeof window !== 'undefined' ? new URLSearchParams(window.location.search) : null;
 const view = params?.get('view');
@@ -15,6 +16,8 @@ if (view === 'floating' && typeof document !== 'undefined') {
   document.documentElement.classList.add('floating-view');
 }
 
+startPlannerDateSync();
+
 ReactDOM.createRoot(document.getElementById('root')!).render(
   <React.StrictMode>
     <I18nProvider>
@@ -63,6 +63,7 @@ export type PersistedAppState = {
   chatHistory: ChatMessage[];
   legacyData: LegacyData;
   reviewHistoryDates: ReviewHistoryDate[];
+  lastRolloverDate: string | null;
   currentTaskId: string | null;
   isAIPanelOpen: boolean;
 };
@@ -135,7 +136,7 @@ export const migrateTask = (raw: Record<string, unknown>): Task => {
 
   const done = raw.isCompleted === true || raw.status === 'done';
   const recurrence = typeof raw.recurrence === 'object' && raw.recurrence
-    ? raw.recurrence as { frequency?: unknown; smartWorkdayOnly?: unknown; endDate?: unknown }
+    ? raw.recurrence as { frequency?: unknown; smartWorkdayOnly?: unknown; endDate?: unknown; lastGeneratedDate?: unknown }
     : null;
   const planningState = normalizePlanningState(raw.planningState) || inferPlanningState(scheduledStart, typeof raw.dueAt === 'string' ? raw.dueAt : scheduledEnd, createdAt);
   const reviewAnchor = scheduledStart || (typeof raw.dueAt === 'string' ? raw.dueAt : undefined) || updatedAt;
@@ -192,8 +193,10 @@ export const migrateTask = (raw: Record<string, unknown>): Task => {
             : 'none',
           smartWorkdayOnly: recurrence.smartWorkdayOnly === true,
           endDate: typeof recurrence.endDate === 'string' ? recurrence.endDate : undefined,
+          lastGeneratedDate: typeof recurrence.lastGeneratedDate === 'string' ? recurrence.lastGeneratedDate : undefined,
         }
       : undefined,
+    seriesId: typeof raw.seriesId === 'string' ? raw.seriesId : undefined,
     linkedGoalIds: Array.isArray(raw.linkedGoalIds)
       ? raw.linkedGoalIds.filter((v): v is string => typeof v === 'string')
       : [],
@@ -345,6 +348,7 @@ export const migrateStore = (persisted: unknown): PersistedAppState => {
     reviewHistoryDates: Array.isArray(raw.reviewHistoryDates)
       ? raw.reviewHistoryDates.filter((item): item is string => typeof item === 'string')
       : [],
+    lastRolloverDate: typeof raw.lastRolloverDate === 'string' ? raw.lastRolloverDate : null,
     isAIPanelOpen: typeof raw.isAIPanelOpen === 'boolean' ? raw.isAIPanelOpen : true,
   };
 };
@@ -1,6 +1,5 @@
 import { create } from 'zustand';
 import { createJSONStorage, persist } from 'zustand/middleware';
-import { invoke } from '@tauri-apps/api/core';
 import { load as loadTauriStore, type Store } from '@tauri-apps/plugin-store';
 import {
   AISettings,
@@ -27,6 +26,9 @@ import {
   STORE_VERSION,
 } from './migrations.js';
 import { isTauriRuntime } from '../utils/runtime.js';
+import { commands } from '../bindings.js';
+import { unwrapResult } from '../utils/commandResult.js';
+import { getPlannerDate } from '../utils/plannerDate.js';
 
 type AppStoreState = {
   schemaVersion: number;
@@ -42,6 +44,7 @@ type AppStoreState = {
   chatHistory: ChatMessage[];
   legacyData: LegacyData;
   reviewHistoryDates: ReviewHistoryDate[];
+  lastRolloverDate: string | null;
   isSettingsOpen: boolean;
   currentTaskId: string | null;
   selectedTaskId: string | null;
@@ -64,6 +67,7 @@ type AppStoreState = {
   applyTodayPlan: (highlightTaskId: string | null, supportTaskIds: string[]) => void;
   applySuggestedShutdown: (payload: { completeTaskIds?: string[]; carryForwardTaskIds?: string[]; dropTaskIds?: string[] }) => void;
   syncTaskRelations: (taskId: string, linkedGoalIds: string[], linkedWeeklyGoalIds: string[]) => void;
+  applyRollover: (sent: Task[], rolled: Task[], date: string) => void;
   deleteTask: (id: string) => void;
   addList: (list: PlannerList) => void;
   updateList: (id: string, updates: Partial<PlannerList>) => void;
@@ -141,6 +145,7 @@ const initialState: Omit<
   chatHistory: [],
   legacyData: {},
   reviewHistoryDates: [],
+  lastRolloverDate: null,
   isSettingsOpen: false,
   currentTaskId: null,
   selectedTaskId: null,
@@ -189,14 +194,12 @@ const readLegacyNativeStoreValue = async (name: string) => {
   if (!isTauriRuntime()) return null;
 
   try {
-    return await invoke<string | null>('load_legacy_native_store_value', { name });
+    return unwrapResult(await commands.loadLegacyNativeStoreValue(name));
   } catch {
     return null;
   }
 };
 
-const todayDate = () => nowIso().slice(0, 10);
-
 const syncGoalWeeklyLinks = (goals: QuarterlyGoal[], weeklyPlans: WeeklyPlan[]) => {
   const weeklyGoalMap = new Map<string, string[]>();
 
@@ -296,7 +299,7 @@ export const useAppStore = create<AppStoreState>()(
         tasks: [
           ...state.tasks,
           task.planningState === 'today' && !task.plannedForDate
-            ? { ...task, plannedForDate: task.scheduledStart?.slice(0, 10) || todayDate() }
+            ? { ...task, plannedForDate: task.scheduledStart?.slice(0, 10) || getPlannerDate() }
             : task,
         ],
       })),
@@ -310,7 +313,7 @@ export const useAppStore = create<AppStoreState>()(
           ? {
               ...task,
               planningState,
-              plannedForDate: planningState === 'today' ? todayDate() : task.plannedForDate,
+              plannedForDate: planningState === 'today' ? getPlannerDate() : task.plannedForDate,
               isHighlight: planningState !== 'today' ? false : task.isHighlight,
               updatedAt: nowIso(),
             }
@@ -330,7 +333,7 @@ export const useAppStore = create<AppStoreState>()(
         tasks: state.tasks.map((task) => ({
           ...task,
           planningState: task.id === id ? 'today' : task.planningState,
-          plannedForDate: task.id === id ? todayDate() : task.plannedForDate,
+          plannedForDate: task.id === id ? getPlannerDate() : task.plannedForDate,
           isHighlight: task.id === id,
           updatedAt: task.id === id || task.isHighlight ? nowIso() : task.updatedAt,
         })),
@@ -342,7 +345,7 @@ export const useAppStore = create<AppStoreState>()(
         const updatedTask: Task = {
           ...task,
           planningState: 'today',
-          plannedForDate: todayDate(),
+          plannedForDate: getPlannerDate(),
           isHighlight: false,
           updatedAt: nowIso(),
         };
@@ -361,7 +364,7 @@ export const useAppStore = create<AppStoreState>()(
           return {
             ...task,
             planningState: plannedToday ? 'today' : task.planningState,
-            plannedForDate: plannedToday ? todayDate() : task.plannedForDate,
+            plannedForDate: plannedToday ? getPlannerDate() : task.plannedForDate,
             isHighlight: task.id === highlightTaskId,
             updatedAt: plannedToday || task.id === highlightTaskId || task.isHighlight ? nowIso() : task.updatedAt,
           };
@@ -420,6 +423,20 @@ export const useAppStore = create<AppStoreState>()(
           })),
         })),
       })),
+      // `rolled` is `sent` after the backend's rollover, followed by the new instances. A task edited or deleted in the
+      // meantime keeps that change and leaves the day open, so the next rollover picks it up again.
+      applyRollover: (sent, rolled, date) => set((state) => {
+        const current = new Set(state.tasks);
+        const replaced = new Map<string, Task>();
+        sent.forEach((task, index) => {
+          if (current.has(task)) replaced.set(task.id, rolled[index]);
+        });
+        const instances = rolled.slice(sent.length).filter((task) => task.seriesId && replaced.has(task.seriesId));
+        return {
+          tasks: [...state.tasks.map((task) => replaced.get(task.id) ?? task), ...instances],
+          lastRolloverDate: replaced.size === sent.length ? date : state.lastRolloverDate,
+        };
+      }),
       deleteTask: (id) => set((state) => ({
         tasks: state.tasks.filter((task) => task.id !== id),
         selectedTaskId: state.selectedTaskId === id ? null : state.selectedTaskId,
@@ -583,6 +600,7 @@ export const useAppStore = create<AppStoreState>()(
         chatHistory: state.chatHistory,
         legacyData: state.legacyData,
         reviewHistoryDates: state.reviewHistoryDates,
+        lastRolloverDate: state.lastRolloverDate,
         isAIPanelOpen: state.isAIPanelOpen,
       }),
     },
@@ -14,6 +14,7 @@ export interface TaskRecurrence {
   frequency: RecurrenceFrequency;
   smartWorkdayOnly: boolean;
   endDate?: string;
+  lastGeneratedDate?: string;
 }
 
 export interface TaskReminder {
@@ -41,6 +42,7 @@ export interface Task {
   tagIds: string[];
   reminder?: TaskReminder;
   recurrence?: TaskRecurrence;
+  seriesId?: string;
   linkedGoalIds: string[];
   linkedWeeklyGoalIds: string[];
   pomodoroSessions: number;
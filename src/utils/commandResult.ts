@@ -0,0 +1,13 @@
+import type { AppError, Result } from '../bindings';
+
+export const describeAppError = (error: AppError) => `${error.kind}: ${error.message}`;
+
+// Turns a command's error result back into a rejection so call sites can keep chaining `.then`/`.catch`.
+export const unwrapResult = <T>(result: Result<T, AppError>): T => {
+  if (result.status === 'error') throw new Error(describeAppError(result.error));
+  return result.data;
+};
+
+export const logCommandError = (error: unknown) => {
+  console.error('Native command failed', error);
+};
@@ -1,4 +1,4 @@
-export type FloatingMode = 'standard' | 'mini';
+export type FloatingMode = 'standard' | 'mini' | 'bar';
 
 type FloatingWindowSize = {
   width: number;
@@ -11,16 +11,19 @@ const sizeKey = (mode: FloatingMode) => `floating-pomodoro-size-${mode}`;
 const defaultSizes: Record<FloatingMode, FloatingWindowSize> = {
   standard: { width: 312, height: 208 },
   mini: { width: 232, height: 56 },
+  bar: { width: 800, height: 6 },
 };
 
 const minSizes: Record<FloatingMode, FloatingWindowSize> = {
   standard: { width: 292, height: 188 },
   mini: { width: 208, height: 56 },
+  bar: { width: 240, height: 4 },
 };
 
 const maxSizes: Record<FloatingMode, FloatingWindowSize> = {
   standard: { width: 560, height: 360 },
   mini: { width: 320, height: 120 },
+  bar: { width: 16384, height: 32 },
 };
 
 const clamp = (value: number, min: number, max: number) => Math.min(max, Math.max(min, value));
@@ -29,8 +32,10 @@ const isValidNumber = (value: unknown): value is number => typeof value === 'num
 
 export const getFloatingDefaultSize = (mode: FloatingMode): FloatingWindowSize => defaultSizes[mode];
 
-export const readFloatingMode = (): FloatingMode =>
-  localStorage.getItem(MODE_KEY) === 'mini' ? 'mini' : 'standard';
+export const normalizeFloatingMode = (mode: string | null | undefined): FloatingMode =>
+  mode === 'mini' || mode === 'bar' ? mode : 'standard';
+
+export const readFloatingMode = (): FloatingMode => normalizeFloatingMode(localStorage.getItem(MODE_KEY));
 
 export const writeFloatingMode = (mode: FloatingMode) => {
   localStorage.setItem(MODE_KEY, mode);
@@ -41,3 +41,5 @@ export const isWorkday = (dateStr: string): boolean => {
   
   return !isWeekend(date);
 };
+
+export const getHolidayConfig = (): HolidayConfig => currentConfig;
@@ -0,0 +1,40 @@
+import { useSyncExternalStore } from 'react';
+import { format } from 'date-fns';
+import { commands, events } from '../bindings.js';
+import { logCommandError, unwrapResult } from './commandResult.js';
+import { isTauriRuntime } from './runtime.js';
+
+// The backend's planner day (`last_date` of the timer state), which starts at the configured `day.start_hour` rather
+// than at midnight. Sessions are logged under it, so "today" everywhere in the UI reads it too.
+let plannerDate: string | null = null;
+const listeners = new Set<() => void>();
+
+const setPlannerDate = (date: string) => {
+  if (!date || date === plannerDate) return;
+  plannerDate = date;
+  listeners.forEach((listener) => listener());
+};
+
+// Falls back to the calendar date in the browser build and before the backend has answered.
+export const getPlannerDate = () => plannerDate ?? format(new Date(), 'yyyy-MM-dd');
+
+const subscribe = (listener: () => void) => {
+  listeners.add(listener);
+  return () => {
+    listeners.delete(listener);
+  };
+};
+
+export const usePlannerDate = () => useSyncExternalStore(subscribe, getPlannerDate);
+
+export const startPlannerDateSync = () => {
+  if (!isTauriRuntime()) return;
+  commands.getPomodoroState()
+    .then(unwrapResult)
+    .then((state) => setPlannerDate(state.last_date))
+    .catch(logCommandError);
+  events.dayRolledOver.listen(({ payload }) => setPlannerDate(payload.to));
+  events.timerEvent.listen(({ payload }) => {
+    if (payload.kind === 'tick') setPlannerDate(payload.last_date);
+  });
+};
@@ -0,0 +1,49 @@
+import { commands, events, type JsonValue, type RolledOverTasks } from '../bindings';
+import { useAppStore } from '../stores/useAppStore';
+import { Task } from '../types';
+import { logCommandError, unwrapResult } from './commandResult';
+import { getHolidayConfig } from './holidays';
+import { isTauriRuntime } from './runtime';
+
+// Tasks travel as plain objects so the backend keeps fields it does not know.
+type TaskFields = Partial<{ [key in string]: JsonValue }>;
+
+const listeners = new Set<(result: RolledOverTasks) => void>();
+
+// The backend rolls the tasks over; they are merged here and saved by the store's own autosave, together with
+// `lastRolloverDate`, so no other writer can overwrite them.
+const rollOver = () => {
+  const { tasks, lastRolloverDate } = useAppStore.getState();
+  commands.rollOverTasks(tasks as unknown as TaskFields[], lastRolloverDate, getHolidayConfig())
+    .then(unwrapResult)
+    .then((result) => {
+      if (!result) return;
+      useAppStore.getState().applyRollover(tasks, result.tasks as unknown as Task[], result.date);
+      if (useAppStore.getState().lastRolloverDate !== result.date) {
+        rollOver();
+        return;
+      }
+      listeners.forEach((listener) => listener(result));
+    })
+    .catch(logCommandError);
+};
+
+export const onRolledOver = (listener: (result: RolledOverTasks) => void) => {
+  listeners.add(listener);
+  return () => {
+    listeners.delete(listener);
+  };
+};
+
+// For the main window once its store has hydrated: catches up on days that passed while it was closed, then follows
+// the timer's `day_rolled_over`. The rollover's own announcement carries its summary and needs no answer.
+export const startRollover = () => {
+  if (!isTauriRuntime()) return undefined;
+  rollOver();
+  const unlisten = events.dayRolledOver.listen(({ payload }) => {
+    if (!payload.tasks) rollOver();
+  });
+  return () => {
+    unlisten.then((fn) => fn());
+  };
+};
@@ -7,6 +7,7 @@ import {
   setMinutes,
 } from 'date-fns';
 import { Task } from '../types/index.js';
+import { getPlannerDate } from './plannerDate.js';
 
 export const parseTaskTime = (dateStr: string, timeValue: string) => {
   if (timeValue.includes('T')) return parseISO(timeValue);
@@ -33,21 +34,39 @@ export const getPlanningState = (task: Task): NonNullable<Task['planningState']>
   if (isTaskBacklog(task)) return 'inbox';
 
   const displayDate = getTaskDisplayDate(task);
-  const today = format(new Date(), 'yyyy-MM-dd');
-  return displayDate <= today ? 'today' : 'later';
+  return displayDate <= getPlannerDate() ? 'today' : 'later';
 };
 
-const todayDate = () => format(new Date(), 'yyyy-MM-dd');
-
-const isActiveTodayCommitment = (task: Task, referenceDate = todayDate()) => {
+const isActiveTodayCommitment = (task: Task, referenceDate = getPlannerDate()) => {
   if (getPlanningState(task) !== 'today') return false;
   if (!task.plannedForDate) return true;
   return task.plannedForDate === referenceDate;
 };
 
+export const createInboxTask = (title: string): Task => {
+  const now = new Date().toISOString();
+  return {
+    id: crypto.randomUUID(),
+    title,
+    status: 'todo',
+    planningState: 'inbox',
+    reviewStatus: 'pending',
+    allDay: false,
+    priority: 'medium',
+    listId: 'inbox',
+    tagIds: [],
+    linkedGoalIds: [],
+    linkedWeeklyGoalIds: [],
+    pomodoroSessions: 0,
+    pomodoroMinutes: 0,
+    createdAt: now,
+    updatedAt: now,
+  };
+};
+
 export const isInboxTask = (task: Task) => task.status === 'todo' && getPlanningState(task) === 'inbox';
 
-export const isTodayTask = (task: Task, referenceDate = todayDate()) => (
+export const isTodayTask = (task: Task, referenceDate = getPlannerDate()) => (
   task.status === 'todo' && isActiveTodayCommitment(task, referenceDate)
 );
 
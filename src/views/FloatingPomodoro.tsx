@@ -1,15 +1,16 @@
 import { type MouseEvent as ReactMouseEvent, useEffect, useMemo, useRef, useState } from 'react';
-import { listen } from '@tauri-apps/api/event';
-import { invoke } from '@tauri-apps/api/core';
 import { LogicalSize } from '@tauri-apps/api/dpi';
 import { getCurrentWindow } from '@tauri-apps/api/window';
+import { getCurrentWebviewWindow } from '@tauri-apps/api/webviewWindow';
 import { EyeOff, FastForward, MoreHorizontal, PanelTop, Pause, Play, RotateCcw } from 'lucide-react';
 import { getFloatingCopy } from '../content/floatingCopy';
 import { useI18n } from '../i18n';
 import { usePomodoro } from '../contexts/PomodoroContext';
 import { useHoldAction } from '../hooks/useHoldAction';
 import { useAppStore } from '../stores/useAppStore';
-import { FloatingMode, normalizeFloatingSize, readFloatingMode, readFloatingSize, writeFloatingMode, writeFloatingSize } from '../utils/floatingWindow';
+import { commands, events } from '../bindings';
+import { logCommandError, unwrapResult } from '../utils/commandResult';
+import { FloatingMode, normalizeFloatingMode, normalizeFloatingSize, readFloatingMode, readFloatingSize, writeFloatingMode, writeFloatingSize } from '../utils/floatingWindow';
 
 type FloatingTheme = 'mist' | 'sage' | 'graphite';
 type LegacyFloatingTheme = 'teal' | 'slate' | 'sunset';
@@ -148,19 +149,22 @@ const readBoundTaskNameFromStorage = () => {
 const FloatingPomodoro = () => {
   const { locale, t } = useI18n();
   const copy = getFloatingCopy(locale);
-  const { timeLeft, isActive, mode, toggleTimer, resetTimer, skipMode, currentTaskName } = usePomodoro();
+  const { timeLeft, isActive, mode, toggleTimer, resetTimer, skipMode, currentTaskName, pomodoroSettings } = usePomodoro();
   const tasks = useAppStore((state) => state.tasks);
   const currentTaskId = useAppStore((state) => state.currentTaskId);
   const currentTask = tasks.find((task) => task.id === currentTaskId);
   const initialPreferences = readPreferences();
   const initialMode = (() => {
     const params = new URLSearchParams(window.location.search);
-    return params.get('mode') === 'mini' ? 'mini' : readFloatingMode();
+    const requested = params.get('mode');
+    return requested ? normalizeFloatingMode(requested) : readFloatingMode();
   })();
 
   const appWindow = useMemo(() => (isTauriWindowAvailable() ? getCurrentWindow() : null), []);
   const [theme, setTheme] = useState<FloatingTheme>(initialPreferences.theme);
   const [opacity, setOpacity] = useState(initialPreferences.opacity);
+  // Inside Tauri the backend applies opacity to the native window, so the shell itself stays opaque.
+  const shellOpacity = appWindow ? 1 : opacity;
   const [floatingMode, setFloatingMode] = useState<FloatingMode>(initialMode);
   const [menu, setMenu] = useState<MenuState>(null);
   const [frame, setFrame] = useState({ width: window.innerWidth, height: window.innerHeight });
@@ -204,28 +208,25 @@ const FloatingPomodoro = () => {
       }
     };
 
-    const onModeChanged = (event: Event) => {
-      const detail = (event as CustomEvent<FloatingMode>).detail;
-      setFloatingMode(detail === 'mini' ? 'mini' : 'standard');
-    };
-
     window.addEventListener('storage', onStorage);
     window.addEventListener('floating-preferences-changed', syncPreferences as EventListener);
-    window.addEventListener('floating-mode-changed', onModeChanged as EventListener);
     setBoundTaskName(readBoundTaskNameFromStorage());
 
     let unlistenPromise: Promise<() => void> | null = null;
     if (isTauriWindowAvailable()) {
-      unlistenPromise = listen<{ theme?: string; opacity?: number }>('floating_preferences_changed', (event) => {
-        setTheme(normalizeTheme(event.payload.theme as FloatingPreferences['theme']));
-        setOpacity(normalizeOpacity(event.payload.opacity));
+      unlistenPromise = events.floatingEvent(getCurrentWebviewWindow()).listen(({ payload }) => {
+        if (payload.kind === 'preferencesChanged') {
+          setTheme(normalizeTheme(payload.theme as FloatingPreferences['theme']));
+          setOpacity(normalizeOpacity(payload.opacity));
+        } else if (payload.kind === 'modeChanged') {
+          setFloatingMode(payload.mode);
+        }
       });
     }
 
     return () => {
       window.removeEventListener('storage', onStorage);
       window.removeEventListener('floating-preferences-changed', syncPreferences as EventListener);
-      window.removeEventListener('floating-mode-changed', onModeChanged as EventListener);
       unlistenPromise?.then((unlisten) => unlisten()).catch(() => undefined);
     };
   }, []);
@@ -284,6 +285,9 @@ const FloatingPomodoro = () => {
     if (!appWindow) return;
 
     const syncConstraints = async () => {
+      // The backend sizes the bar from monitor geometry.
+      if (floatingMode === 'bar') return;
+
       if (floatingMode === 'mini') {
         const miniHeight = menu ? 120 : 56;
         await appWindow.setSizeConstraints({
@@ -340,7 +344,7 @@ const FloatingPomodoro = () => {
     setMenu(null);
     if (!appWindow) return;
     const nextSize = readFloatingSize(nextMode);
-    invoke('open_floating_mode', { mode: nextMode, width: nextSize.width, height: nextSize.height }).catch(() => undefined);
+    commands.openFloatingMode(nextMode, nextSize.width, nextSize.height).then(unwrapResult).catch(logCommandError);
   };
 
   const openMenu = (event: ReactMouseEvent<HTMLDivElement>) => {
@@ -353,13 +357,39 @@ const FloatingPomodoro = () => {
     top: Math.max(8, Math.min(menuState?.y || 8, window.innerHeight - menuHeight - 8)),
   });
 
+  if (floatingMode === 'bar') {
+    const totalMinutes = mode === 'work'
+      ? pomodoroSettings.workDuration
+      : mode === 'shortBreak' ? pomodoroSettings.shortBreakDuration : pomodoroSettings.longBreakDuration;
+    const remaining = Math.min(1, Math.max(0, timeLeft / Math.max(1, totalMinutes * 60)));
+    const vertical = window.innerHeight > window.innerWidth;
+
+    return (
+      <div
+        data-testid="floating-bar"
+        data-theme={theme}
+        className="relative h-screen w-screen overflow-hidden"
+        style={{ opacity: shellOpacity, background: palette.shell }}
+        title={`${modeLabel} ${minutes}:${seconds}`}
+        onDoubleClick={() => switchMode('standard')}
+      >
+        <div
+          className="absolute bottom-0 left-0 transition-all duration-1000 ease-linear"
+          style={vertical
+            ? { width: '100%', height: `${remaining * 100}%`, background: palette.accent }
+            : { height: '100%', width: `${remaining * 100}%`, background: palette.accent }}
+        />
+      </div>
+    );
+  }
+
   if (compact) {
     return (
       <div
         data-testid="floating-shell"
         data-theme={theme}
         className="h-screen w-screen overflow-visible"
-        style={{ opacity, background: palette.shell }}
+        style={{ opacity: shellOpacity, background: palette.shell }}
         onClick={() => setMenu(null)}
         onContextMenu={openMenu}
       >
@@ -460,7 +490,7 @@ const FloatingPomodoro = () => {
       data-testid="floating-shell"
       data-theme={theme}
       className="h-screen w-screen overflow-visible"
-      style={{ opacity, background: palette.shell }}
+      style={{ opacity: shellOpacity, background: palette.shell }}
       onClick={() => setMenu(null)}
       onContextMenu={openMenu}
     >
@@ -514,7 +544,7 @@ const FloatingPomodoro = () => {
 
       {menu && (
         <div className={`absolute z-50 min-w-[196px] rounded-2xl border p-1.5 shadow-2xl ${palette.menu}`} style={menuPosition(menu, 204, 162)} onClick={(event) => event.stopPropagation()}>
-          <button type="button" data-testid="floating-menu-settings" className="flex w-full items-center rounded-xl px-3 py-2 text-left text-sm text-slate-700 transition hover:bg-slate-100" onClick={() => { setMenu(null); invoke('open_floating_settings').catch(() => undefined); }}>
+          <button type="button" data-testid="floating-menu-settings" className="flex w-full items-center rounded-xl px-3 py-2 text-left text-sm text-slate-700 transition hover:bg-slate-100" onClick={() => { setMenu(null); commands.openFloatingSettings().then(unwrapResult).catch(logCommandError); }}>
             {copy.menu.appearance}
           </button>
           <button type="button" data-testid="floating-menu-mini" className="flex w-full items-center rounded-xl px-3 py-2 text-left text-sm text-slate-700 transition hover:bg-slate-100" onClick={() => { setMenu(null); switchMode('mini'); }}>
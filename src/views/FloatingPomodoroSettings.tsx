@@ -1,9 +1,10 @@
 import { useEffect, useState } from 'react';
-import { invoke } from '@tauri-apps/api/core';
 import { getCurrentWindow } from '@tauri-apps/api/window';
 import { MonitorUp, MoonStar, SunMedium } from 'lucide-react';
 import { getFloatingCopy } from '../content/floatingCopy';
 import { useI18n } from '../i18n';
+import { commands } from '../bindings';
+import { logCommandError, unwrapResult } from '../utils/commandResult';
 
 type FloatingTheme = 'mist' | 'sage' | 'graphite';
 type LegacyFloatingTheme = 'teal' | 'slate' | 'sunset';
@@ -46,7 +47,7 @@ const FloatingPomodoroSettings = () => {
   useEffect(() => {
     localStorage.setItem('floating-pomodoro-preferences', JSON.stringify({ theme, opacity }));
     window.dispatchEvent(new Event('floating-preferences-changed'));
-    invoke('broadcast_floating_preferences', { theme, opacity }).catch(() => undefined);
+    commands.broadcastFloatingPreferences(theme, opacity).then(unwrapResult).catch(logCommandError);
   }, [theme, opacity]);
 
   const closeWindow = () => {
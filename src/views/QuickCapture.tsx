@@ -0,0 +1,61 @@
+import { useEffect, useRef, useState } from 'react';
+import { Inbox } from 'lucide-react';
+import { getCurrentWindow } from '@tauri-apps/api/window';
+import { useI18n } from '../i18n';
+import { commands } from '../bindings';
+import { logCommandError, unwrapResult } from '../utils/commandResult';
+
+const isTauriWindowAvailable = () => typeof window !== 'undefined' && '__TAURI_INTERNALS__' in window;
+
+const QuickCapture = () => {
+  const { t } = useI18n();
+  const [text, setText] = useState('');
+  const inputRef = useRef<HTMLInputElement>(null);
+
+  useEffect(() => {
+    inputRef.current?.focus();
+  }, []);
+
+  const closeWindow = () => {
+    if (!isTauriWindowAvailable()) return;
+    getCurrentWindow().close().catch(() => undefined);
+  };
+
+  // The backend queues the entry for the main window's Inbox and closes this window.
+  const submit = () => {
+    if (!text.trim()) {
+      closeWindow();
+      return;
+    }
+    commands.submitCapture(text).then(unwrapResult).catch(logCommandError);
+  };
+
+  return (
+    <div data-testid="quick-capture-view" className="flex h-screen w-screen items-center bg-transparent p-2" data-tauri-drag-region>
+      <div className="flex w-full items-center gap-3 rounded-2xl border border-slate-200 bg-white px-4 py-3 shadow-lg">
+        <Inbox size={18} className="shrink-0 text-slate-400" />
+        <input
+          ref={inputRef}
+          value={text}
+          onChange={(event) => setText(event.target.value)}
+          onKeyDown={(event) => {
+            if (event.key === 'Enter' && !event.nativeEvent.isComposing) {
+              event.preventDefault();
+              submit();
+            } else if (event.key === 'Escape') {
+              event.preventDefault();
+              closeWindow();
+            }
+          }}
+          onBlur={closeWindow}
+          placeholder={t('capture.placeholder')}
+          aria-label={t('capture.placeholder')}
+          className="min-w-0 flex-1 bg-transparent text-base text-slate-900 outline-none placeholder:text-slate-400"
+        />
+        <span className="shrink-0 text-xs text-slate-400">{t('capture.hint')}</span>
+      </div>
+    </div>
+  );
+};
+
+export default QuickCapture;
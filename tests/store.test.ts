@@ -18,6 +18,7 @@ const resetStore = () => {
     aiSettings: defaultAISettings,
     chatHistory: [],
     legacyData: {},
+    lastRolloverDate: null,
     isSettingsOpen: false,
     currentTaskId: null,
     selectedTaskId: null,
@@ -228,3 +229,30 @@ test('promoteTaskToSupport moves a later task into today right after the highlig
   assert.equal(state.tasks[1].planningState, 'today');
   assert.equal(state.tasks[1].isHighlight, false);
 });
+
+test('applyRollover keeps tasks edited during the rollover and leaves the day open for them', () => {
+  const series = baseTask({ id: 'task-series', recurrence: { frequency: 'daily', smartWorkdayOnly: false } });
+  const edited = baseTask({ id: 'task-edited', planningState: 'today' });
+  useAppStore.setState({ tasks: [series, edited] });
+  const sent = useAppStore.getState().tasks;
+  const rolled = [
+    { ...series, recurrence: { frequency: 'daily', smartWorkdayOnly: false, lastGeneratedDate: '2026-03-17' } },
+    { ...edited, reviewStatus: 'pending' },
+    baseTask({ id: 'task-instance', seriesId: 'task-series', plannedForDate: '2026-03-17' }),
+  ] as Task[];
+
+  useAppStore.getState().updateTask('task-edited', { title: 'renamed' });
+  useAppStore.getState().applyRollover(sent, rolled, '2026-03-17');
+  let state = useAppStore.getState();
+
+  assert.deepEqual(state.tasks.map((task) => task.id), ['task-series', 'task-edited', 'task-instance']);
+  assert.equal(state.tasks[0].recurrence?.lastGeneratedDate, '2026-03-17');
+  assert.equal(state.tasks[1].title, 'renamed');
+  assert.equal(state.lastRolloverDate, null);
+
+  const resent = state.tasks;
+  useAppStore.getState().applyRollover(resent, resent.map((task) => ({ ...task })), '2026-03-17');
+  state = useAppStore.getState();
+  assert.equal(state.tasks.length, 3);
+  assert.equal(state.lastRolloverDate, '2026-03-17');
+});